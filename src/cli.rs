@@ -0,0 +1,229 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Command line arguments of the simulator.
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser, Debug)]
+#[command(version, about, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Args {
+  #[command(subcommand)]
+  pub command: Option<Command>,
+  /// Arguments of the main command, only present without a subcommand.
+  #[command(flatten)]
+  pub run: Option<RunArgs>,
+}
+
+/// Subcommands of the simulator. Without any, the strings are run on the Turing machine.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+  /// Run the test cases of a Turing machine, reporting which ones failed.
+  Test(TestArgs),
+  /// Run a string step by step in an interactive debugger.
+  Debug(DebugArgs),
+  /// Visualize a run of a string in a full-screen terminal view.
+  Tui(TuiArgs),
+  /// Check a Turing machine configuration without running it, reporting every problem found.
+  Check(CheckArgs),
+  /// Convert a JFLAP, turingmachine.io or Morphett machine to a toml configuration.
+  Import(ImportArgs),
+  /// Convert a Turing machine to a JFLAP file, laying out its states.
+  Export(ExportArgs),
+  /// Print the state diagram of a Turing machine in the DOT language of Graphviz.
+  Graph(GraphArgs),
+  /// Write a self-contained HTML page that replays a run of a string step by step.
+  Replay(ReplayArgs),
+  /// Print the state diagram of a Turing machine, or the configurations of a run, for LaTeX.
+  Tikz(TikzArgs),
+}
+
+/// Arguments to locate the Turing machine file, shared by every subcommand.
+#[derive(clap::Args, Debug)]
+pub struct SourceArgs {
+  /// Path where the Turing machine configuration is located.
+  #[arg(short, long)]
+  pub turing_path: String,
+  /// Format of the Turing machine file, detected from its extension if not given.
+  #[arg(long, value_enum)]
+  pub format: Option<FormatArg>,
+}
+
+/// Arguments to load and configure the Turing machine, shared by the subcommands that run it.
+#[derive(clap::Args, Debug)]
+pub struct MachineArgs {
+  #[command(flatten)]
+  pub source: SourceArgs,
+  /// Maximum steps of a run.
+  #[arg(long)]
+  pub max_steps: Option<usize>,
+  /// Maximum wall-clock time of a run, in milliseconds.
+  #[arg(long)]
+  pub timeout: Option<u64>,
+  /// Maximum tape cells used by a run, adding up every tape.
+  #[arg(long)]
+  pub max_cells: Option<usize>,
+  /// Maximum configurations explored by a nondeterministic run.
+  #[arg(short, long)]
+  pub budget: Option<usize>,
+  /// Cancel deterministic runs that repeat a configuration, as they would never halt.
+  #[arg(long)]
+  pub detect_cycles: bool,
+}
+
+/// Arguments to run strings on the Turing machine.
+/// The strings make up the group, as the ones of the flattened arguments can't be part of it.
+#[derive(clap::Args, Debug)]
+#[group(id = "run")]
+pub struct RunArgs {
+  /// Strings to be tested on the Turing machine.
+  #[arg(required_unless_present = "input", group = "run")]
+  pub strings: Vec<String>,
+  /// File with the strings to be tested, one per line ("-" to read them from stdin).
+  #[arg(short, long, group = "run")]
+  pub input: Option<String>,
+  #[command(flatten)]
+  pub machine: MachineArgs,
+  /// File where the state of the tapes will be dumped.
+  #[arg(short, long)]
+  pub dump: Option<String>,
+  /// Format of the dumped execution trace.
+  #[arg(long, value_enum, default_value_t = TraceFormatArg::Text, requires = "dump")]
+  pub trace_format: TraceFormatArg,
+  /// Include the full content of the tapes in each record of the jsonl trace.
+  #[arg(long, requires = "dump")]
+  pub trace_tapes: bool,
+  /// Return a distinct exit code for each outcome (0 accepted, 2 rejected, 3 halted, 4-6 limits, 7 loop).
+  #[arg(short, long, action = clap::ArgAction::Count)]
+  pub shell: u8,
+  /// Print the path of the branch that accepted the string.
+  #[arg(short, long)]
+  pub path: bool,
+  /// Print the content of the output tape instead of the outcome.
+  #[arg(short, long, conflicts_with = "path")]
+  pub output: bool,
+  /// Print the outcome in addition to the content of the output tape.
+  #[arg(short, long, requires = "output")]
+  pub verbose: bool,
+}
+
+/// Formats of the execution trace.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceFormatArg {
+  /// Human-readable text, with the state of the tapes after each step.
+  #[default]
+  Text,
+  /// JSON Lines, one object per step.
+  Jsonl,
+}
+
+/// Formats of the Turing machine files.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormatArg {
+  /// Configuration of this simulator (.toml).
+  Toml,
+  /// JFLAP file (.jff).
+  Jflap,
+  /// YAML of turingmachine.io (.yaml, .yml).
+  Yaml,
+  /// Rules of morphett.info (.tm).
+  Morphett,
+}
+
+/// Arguments of the test subcommand.
+#[derive(clap::Args, Debug)]
+pub struct TestArgs {
+  #[command(flatten)]
+  pub machine: MachineArgs,
+  /// Additional files with test cases. The ones in the configuration are always run.
+  #[arg(short = 'S', long)]
+  pub suite: Vec<String>,
+}
+
+/// Arguments of the debug subcommand.
+#[derive(clap::Args, Debug)]
+pub struct DebugArgs {
+  /// String to be debugged on the Turing machine.
+  #[arg(default_value = "")]
+  pub string: String,
+  #[command(flatten)]
+  pub machine: MachineArgs,
+}
+
+/// Arguments of the tui subcommand.
+#[derive(clap::Args, Debug)]
+pub struct TuiArgs {
+  /// String to be visualized on the Turing machine.
+  #[arg(default_value = "")]
+  pub string: String,
+  #[command(flatten)]
+  pub machine: MachineArgs,
+  /// Initial speed while playing, in steps per second.
+  #[arg(long, default_value_t = 10)]
+  pub speed: u32,
+}
+
+/// Arguments of the check subcommand.
+#[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+  #[command(flatten)]
+  pub source: SourceArgs,
+}
+
+/// Arguments of the import subcommand.
+#[derive(clap::Args, Debug)]
+pub struct ImportArgs {
+  #[command(flatten)]
+  pub source: SourceArgs,
+  /// File where the toml configuration is written, instead of the standard output.
+  #[arg(short, long)]
+  pub output: Option<String>,
+}
+
+/// Arguments of the export subcommand.
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs {
+  #[command(flatten)]
+  pub source: SourceArgs,
+  /// File where the JFLAP file (.jff) is written, instead of the standard output.
+  #[arg(short, long)]
+  pub output: Option<String>,
+}
+
+/// Arguments of the graph subcommand.
+#[derive(clap::Args, Debug)]
+pub struct GraphArgs {
+  #[command(flatten)]
+  pub source: SourceArgs,
+  /// File where the DOT digraph is written, instead of the standard output.
+  #[arg(short, long)]
+  pub output: Option<String>,
+}
+
+/// Arguments of the replay subcommand.
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+  /// String to be replayed on the Turing machine.
+  #[arg(default_value = "")]
+  pub string: String,
+  #[command(flatten)]
+  pub machine: MachineArgs,
+  /// File where the HTML page is written, instead of the standard output.
+  #[arg(short, long)]
+  pub output: Option<String>,
+}
+
+/// Arguments of the tikz subcommand.
+#[derive(clap::Args, Debug)]
+pub struct TikzArgs {
+  #[command(flatten)]
+  pub machine: MachineArgs,
+  /// String whose sequence of configurations is printed, instead of the state diagram.
+  #[arg(short, long)]
+  pub configurations: Option<String>,
+  /// File where the LaTeX code is written, instead of the standard output.
+  #[arg(short, long)]
+  pub output: Option<String>,
+}
@@ -0,0 +1,47 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Check subcommand: static analysis of a Turing machine configuration.
+
+use crate::cli::CheckArgs;
+use crate::parse_raw;
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::error::{Located, snippet};
+use turing_machine_simulator::lint::{self, Severity};
+use turing_machine_simulator::{Error, parser};
+
+/// Check the configuration, printing every problem found.
+/// Fails if any of them prevents loading the configuration.
+pub fn check(args: &CheckArgs) -> Result<ExitCode, Error> {
+  let path = &args.source.turing_path;
+  let raw = fs::read_to_string(path).map_err(|x| Error::OpenFile(x.to_string()))?;
+  let rtm = parse_raw(path, args.source.format, &raw)?;
+  let diagnostics = lint::check(&rtm);
+  for x in &diagnostics {
+    let location = match &x.span {
+      | Some(span) => snippet(path, &raw, span),
+      | None => format!(" --> {path}"),
+    };
+    println!("{x}\n{location}\n");
+  }
+  let mut errors = diagnostics.iter().filter(|x| x.severity == Severity::Error).count();
+  // Anything not covered by the analysis is still reported by the parser.
+  if errors == 0
+    && let Err(x) = parser::parse(&rtm)
+  {
+    let located = match x {
+      | Error::Invalid(x) => x,
+      | x => vec![Located { error: x, transition: None, span: None }],
+    };
+    for x in &located {
+      println!("{}\n", x.render(path, &raw));
+    }
+    errors += located.len();
+  }
+  let warnings = diagnostics.iter().filter(|x| x.severity == Severity::Warning).count();
+  println!("Summary: {errors} errors, {warnings} warnings.");
+  if errors > 0 { Ok(ExitCode::FAILURE) } else { Ok(ExitCode::SUCCESS) }
+}
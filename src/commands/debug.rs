@@ -0,0 +1,310 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Debug subcommand: interactive step by step debugger.
+
+use crate::cli::DebugArgs;
+use crate::{format_transition, load_machine};
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+use std::time::Instant;
+use turing_machine_simulator::turing_machine::Execution;
+use turing_machine_simulator::{Error, Outcome, TuringMachine};
+
+/// Help message of the debugger.
+const HELP: &str = "\
+Commands:
+  step [n]              Do n steps (1 by default).
+  continue              Run until a breakpoint is hit, the machine halts or a limit is reached.
+  back [n]              Undo n steps (1 by default).
+  back state <state>    Undo steps until the last previous visit of the state.
+  break state <state>   Stop when entering the state.
+  break read <symbols>  Stop when the symbols under the heads are the given ones (one per tape,
+                        separated by spaces if any of them has more than one character).
+  breakpoints           List the breakpoints.
+  delete <n>            Delete the breakpoint n.
+  tapes                 Print the tapes.
+  info                  Print the current state and the transition about to fire.
+  restart [string]      Restart the run, optionally with another string.
+  help                  Print this message.
+  quit                  Exit the debugger.";
+
+/// Conditions that stop the execution when continuing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Breakpoint {
+  /// Stop when entering the state.
+  State(usize),
+  /// Stop when the symbols under the heads are the given ones.
+  Read(Vec<char>),
+}
+
+impl Breakpoint {
+  /// Returns true if the execution must stop after the last step.
+  fn hit(&self, execution: &Execution) -> bool {
+    match self {
+      | Breakpoint::State(x) => execution.state() == *x,
+      | Breakpoint::Read(x) => execution.read() == *x,
+    }
+  }
+
+  /// Returns the human-readable representation of the breakpoint.
+  fn describe(&self, tm: &TuringMachine) -> String {
+    match self {
+      | Breakpoint::State(x) => format!("state {}", tm.state_name(*x)),
+      | Breakpoint::Read(x) => format!("read [{}]", tm.symbols().format(x)),
+    }
+  }
+}
+
+/// Print the current state and the transition about to fire, or the outcome if halted.
+fn print_info(execution: &Execution) {
+  let tm = execution.machine();
+  println!("Step {}, state {}", execution.steps(), tm.state_name(execution.state()));
+  match (execution.outcome(), execution.next_transition()) {
+    | (Some(outcome), _) => println!("Halted: {outcome}"),
+    | (None, Some(tr)) => {
+      println!("Next: {}", format_transition(tm, execution.state(), &execution.read(), &tr))
+    },
+    | (None, None) => {},
+  }
+}
+
+/// Print the tapes, naming the symbols with the table of the machine.
+fn print_tapes(execution: &Execution) {
+  for (i, tape) in execution.tapes().iter().enumerate() {
+    println!("{i}: {}", tape.render(execution.machine().symbols()));
+  }
+}
+
+/// Returns the limit of the machine reached before the next step, printing it, if any.
+/// The steps are counted from the start of the run, and the time from the start of the command.
+fn stopped(execution: &Execution, start: &Instant) -> Option<Outcome> {
+  let limits = execution.machine().limits();
+  let outcome = limits.exceeded(execution.steps(), start, execution.tapes())?;
+  println!("Stopped: {outcome}.");
+  Some(outcome)
+}
+
+/// Do up to `n` steps, stopping if the machine halts or any limit of the machine is reached.
+/// Returns the limit reached, if any.
+fn step(execution: &mut Execution, n: usize) -> Option<Outcome> {
+  let start = Instant::now();
+  for _ in 0..n {
+    if let Some(outcome) = stopped(execution, &start) {
+      return Some(outcome);
+    }
+    if !execution.step() {
+      break;
+    }
+  }
+  None
+}
+
+/// Run until a breakpoint is hit, the machine halts or any limit of the machine is reached.
+/// Returns the breakpoint hit, if any.
+fn continue_run(execution: &mut Execution, breakpoints: &[Breakpoint]) -> Option<usize> {
+  let start = Instant::now();
+  while stopped(execution, &start).is_none() && execution.step() {
+    if let Some(x) = breakpoints.iter().position(|x| x.hit(execution)) {
+      println!("Breakpoint {x}: {}", breakpoints[x].describe(execution.machine()));
+      return Some(x);
+    }
+  }
+  None
+}
+
+/// Returns the state with the given name, or with the given index if it exists.
+fn parse_state(tm: &TuringMachine, name: &str) -> Option<usize> {
+  tm.state_index(name).or_else(|| name.parse().ok().filter(|x| *x < tm.nstates()))
+}
+
+/// Parse a breakpoint from the arguments of the break command.
+fn parse_breakpoint(tm: &TuringMachine, args: &[&str]) -> Result<Breakpoint, String> {
+  match args {
+    | ["state", name] => match parse_state(tm, name) {
+      | Some(x) => Ok(Breakpoint::State(x)),
+      | None => Err(format!("Unknown state: {name}.")),
+    },
+    | ["read", names @ ..] if !names.is_empty() => {
+      // A single argument has a character per symbol, otherwise each one is a symbol name.
+      let names: Vec<String> = match names {
+        | [x] => x.chars().map(String::from).collect(),
+        | x => x.iter().map(|x| x.to_string()).collect(),
+      };
+      if names.len() != tm.ntapes() {
+        return Err(format!("Expected {} symbols, one per tape.", tm.ntapes()));
+      }
+      let symbols =
+        names.iter().map(|x| tm.symbols().get(x).ok_or(format!("Unknown symbol: {x}.")));
+      Ok(Breakpoint::Read(symbols.collect::<Result<_, _>>()?))
+    },
+    | _ => Err("Usage: break state <state> | break read <symbols>.".to_string()),
+  }
+}
+
+/// Run the interactive debugger, reading the commands from the standard input.
+pub fn debug(args: &DebugArgs) -> Result<ExitCode, Error> {
+  let turing_machine = load_machine(&args.machine)?;
+  turing_machine.check_input(&args.string)?;
+  let mut execution = turing_machine.execution(&args.string);
+  let mut breakpoints: Vec<Breakpoint> = Vec::new();
+  println!("Debugging \"{}\". Type \"help\" to list the commands.", args.string);
+  print_info(&execution);
+  print_tapes(&execution);
+  let mut lines = io::stdin().lock().lines();
+  loop {
+    print!("(tm) ");
+    io::stdout().flush().map_err(|x| Error::WriteFile(x.to_string()))?;
+    let line = match lines.next() {
+      | Some(x) => x.map_err(|x| Error::OpenFile(x.to_string()))?,
+      | None => break,
+    };
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+      | [] => continue,
+      | ["step" | "s"] => {
+        step(&mut execution, 1);
+      },
+      | ["step" | "s", n] => match n.parse() {
+        | Ok(n) => {
+          step(&mut execution, n);
+        },
+        | Err(_) => println!("Invalid number of steps: {n}."),
+      },
+      | ["continue" | "c"] => {
+        continue_run(&mut execution, &breakpoints);
+      },
+      | ["back" | "u"] => {
+        execution.step_back();
+      },
+      | ["back" | "u", "state", name] => match parse_state(&turing_machine, name) {
+        | Some(x) if execution.rewind_to_state(x) => {},
+        | _ => {
+          println!("The state {name} hasn't been visited before.");
+          continue;
+        },
+      },
+      | ["back" | "u", n] => match n.parse() {
+        | Ok(n) => {
+          execution.rewind(n);
+        },
+        | Err(_) => println!("Invalid number of steps: {n}."),
+      },
+      | ["break" | "b", rest @ ..] => match parse_breakpoint(&turing_machine, rest) {
+        | Ok(x) => {
+          println!("Breakpoint {}: {}", breakpoints.len(), x.describe(&turing_machine));
+          breakpoints.push(x);
+          continue;
+        },
+        | Err(x) => {
+          println!("{x}");
+          continue;
+        },
+      },
+      | ["breakpoints"] => {
+        for (i, x) in breakpoints.iter().enumerate() {
+          println!("Breakpoint {i}: {}", x.describe(&turing_machine));
+        }
+        continue;
+      },
+      | ["delete" | "d", n] => {
+        match n.parse::<usize>() {
+          | Ok(n) if n < breakpoints.len() => {
+            breakpoints.remove(n);
+          },
+          | _ => println!("Unknown breakpoint: {n}."),
+        }
+        continue;
+      },
+      | ["tapes" | "t"] => {
+        print_tapes(&execution);
+        continue;
+      },
+      | ["info" | "i"] => {},
+      | ["restart" | "r"] => execution.restart(),
+      | ["restart" | "r", string] => match turing_machine.check_input(string) {
+        | Ok(()) => execution = turing_machine.execution(string),
+        | Err(x) => {
+          println!("{x}");
+          continue;
+        },
+      },
+      | ["help" | "h"] => {
+        println!("{HELP}");
+        continue;
+      },
+      | ["quit" | "q"] => break,
+      | _ => {
+        println!("Unknown command: {line}. Type \"help\" to list the commands.");
+        continue;
+      },
+    }
+    print_info(&execution);
+    print_tapes(&execution);
+  }
+  Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::commands::debug::{Breakpoint, continue_run, parse_breakpoint, step};
+  use std::time::Duration;
+  use turing_machine_simulator::{Limits, Outcome, parser};
+
+  /// Machine that walks right over the a's, staying in the state 0, and accepts at the end.
+  const MACHINE: &str = r#"
+    ntapes = 1
+    initial = 0
+    accept = [1]
+
+    [[transition]]
+    from = 0
+    next = 0
+    read = "a"
+    write = "a"
+    direction = "R"
+
+    [[transition]]
+    from = 0
+    next = 1
+    read = "β"
+    write = "β"
+    direction = "S"
+  "#;
+
+  #[test]
+  fn test_breakpoints() {
+    let tm = parser::from_str(MACHINE).unwrap();
+    assert_eq!(parse_breakpoint(&tm, &["state", "0"]), Ok(Breakpoint::State(0)));
+    assert_eq!(parse_breakpoint(&tm, &["state", "7"]), Err("Unknown state: 7.".to_string()));
+    // The breakpoint fires on every step entering the state, even from itself.
+    let mut execution = tm.execution("aaa");
+    let breakpoints = [Breakpoint::State(0)];
+    assert_eq!(continue_run(&mut execution, &breakpoints), Some(0));
+    assert_eq!(continue_run(&mut execution, &breakpoints), Some(0));
+    assert_eq!(execution.steps(), 2);
+    assert_eq!(continue_run(&mut execution, &breakpoints), Some(0));
+    assert_eq!(continue_run(&mut execution, &breakpoints), None);
+    assert_eq!(execution.outcome(), Some(Outcome::Accepted));
+  }
+
+  #[test]
+  fn test_limits() {
+    let mut tm = parser::from_str(MACHINE).unwrap();
+    tm.set_limits(&Limits { steps: 2, ..Limits::default() });
+    let mut execution = tm.execution("aaa");
+    assert_eq!(step(&mut execution, 5), Some(Outcome::StepLimit));
+    assert_eq!(execution.steps(), 2);
+    assert_eq!(step(&mut execution, 1), Some(Outcome::StepLimit));
+    assert_eq!(continue_run(&mut execution, &[]), None);
+    assert_eq!(execution.steps(), 2);
+    tm.set_limits(&Limits { timeout: Some(Duration::ZERO), ..Limits::default() });
+    assert_eq!(step(&mut tm.execution("aaa"), 1), Some(Outcome::Timeout));
+    tm.set_limits(&Limits { cells: Some(3), ..Limits::default() });
+    let mut execution = tm.execution("aaa");
+    assert_eq!(step(&mut execution, 5), Some(Outcome::SpaceLimit));
+    assert_eq!(execution.steps(), 3);
+  }
+}
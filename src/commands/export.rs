@@ -0,0 +1,26 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Export subcommand: conversion of a Turing machine to a JFLAP file.
+
+use crate::cli::ExportArgs;
+use crate::read_machine;
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::{Error, jflap};
+
+/// Write the JFLAP file of the machine.
+pub fn export(args: &ExportArgs) -> Result<ExitCode, Error> {
+  let turing_machine = read_machine(&args.source)?;
+  let xml = jflap::to_string(&turing_machine)?;
+  for x in jflap::warnings(&turing_machine) {
+    eprintln!("warning: {x}");
+  }
+  match &args.output {
+    | Some(output) => fs::write(output, xml).map_err(|x| Error::WriteFile(x.to_string()))?,
+    | None => print!("{xml}"),
+  }
+  Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,22 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Graph subcommand: state diagram of a Turing machine, for Graphviz.
+
+use crate::cli::GraphArgs;
+use crate::read_machine;
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::{Error, dot};
+
+/// Write the DOT digraph of the machine.
+pub fn graph(args: &GraphArgs) -> Result<ExitCode, Error> {
+  let dot = dot::to_string(&read_machine(&args.source)?);
+  match &args.output {
+    | Some(output) => fs::write(output, dot).map_err(|x| Error::WriteFile(x.to_string()))?,
+    | None => print!("{dot}"),
+  }
+  Ok(ExitCode::SUCCESS)
+}
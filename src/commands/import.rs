@@ -0,0 +1,26 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Import subcommand: conversion of a machine of another simulator to a toml configuration.
+
+use crate::cli::ImportArgs;
+use crate::{parse_raw, print_errors};
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::{Error, parser};
+
+/// Write the toml configuration of the machine, checking it is valid first.
+pub fn import(args: &ImportArgs) -> Result<ExitCode, Error> {
+  let path = &args.source.turing_path;
+  let source = fs::read_to_string(path).map_err(|x| Error::OpenFile(x.to_string()))?;
+  let raw = parse_raw(path, args.source.format, &source)?;
+  parser::parse(&raw).inspect_err(|x| print_errors(path, &source, x))?;
+  let toml = parser::to_toml(&raw)?;
+  match &args.output {
+    | Some(output) => fs::write(output, toml).map_err(|x| Error::WriteFile(x.to_string()))?,
+    | None => print!("{toml}"),
+  }
+  Ok(ExitCode::SUCCESS)
+}
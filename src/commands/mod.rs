@@ -0,0 +1,16 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Subcommands of the simulator.
+
+pub mod check;
+pub mod debug;
+pub mod export;
+pub mod graph;
+pub mod import;
+pub mod replay;
+pub mod test;
+pub mod tikz;
+pub mod tui;
@@ -0,0 +1,22 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Replay subcommand: HTML page that replays a run.
+
+use crate::cli::ReplayArgs;
+use crate::load_machine;
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::{Error, replay};
+
+/// Write the HTML replay of the run of the string.
+pub fn replay(args: &ReplayArgs) -> Result<ExitCode, Error> {
+  let html = replay::to_html(&load_machine(&args.machine)?, &args.string)?;
+  match &args.output {
+    | Some(output) => fs::write(output, html).map_err(|x| Error::WriteFile(x.to_string()))?,
+    | None => print!("{html}"),
+  }
+  Ok(ExitCode::SUCCESS)
+}
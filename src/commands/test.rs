@@ -0,0 +1,72 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Test subcommand: run the test cases of a Turing machine.
+
+use crate::cli::{FormatArg, TestArgs};
+use crate::{file_format, load_machine};
+use std::process::ExitCode;
+use turing_machine_simulator::Error;
+use turing_machine_simulator::suite::{self, TestReport};
+
+/// Print the expected and the obtained content of the output tape, pointing the first difference.
+fn print_diff(expected: &str, got: &str) {
+  let position = expected.chars().zip(got.chars()).take_while(|(x, y)| x == y).count();
+  println!("    expected: \"{expected}\"");
+  println!("    got:      \"{got}\"");
+  println!("               {}^", " ".repeat(position));
+}
+
+/// Print the result of a single test case.
+fn print_report(report: &TestReport) {
+  let input = &report.case.input;
+  match &report.outcome {
+    | Ok(outcome) if report.passed() => {
+      println!("PASS \"{input}\" -> {outcome}");
+      return;
+    },
+    | Ok(outcome) if !report.outcome_matches() => {
+      println!("FAIL \"{input}\"");
+      println!("    expected {}, got {outcome}", report.case.expect);
+    },
+    | Ok(_) => println!("FAIL \"{input}\""),
+    | Err(x) => {
+      println!("FAIL \"{input}\"");
+      println!("    expected {}, got an error: {x}", report.case.expect);
+    },
+  }
+  if !report.output_matches() {
+    let expected = report.case.output.as_deref().unwrap_or_default();
+    match &report.output {
+      | Some(output) => print_diff(expected, output),
+      | None => println!("    expected output \"{expected}\", got no output"),
+    }
+  }
+}
+
+/// Run every test case, from the configuration and the additional suites.
+/// Returns a failure exit code if any of them failed.
+pub fn test(args: &TestArgs) -> Result<ExitCode, Error> {
+  let turing_machine = load_machine(&args.machine)?;
+  // Only toml configurations can have test cases.
+  let mut cases = Vec::new();
+  let source = &args.machine.source;
+  if file_format(&source.turing_path, source.format) == FormatArg::Toml {
+    cases = suite::from_file(&source.turing_path)?;
+  }
+  for path in &args.suite {
+    cases.extend(suite::from_file(path)?);
+  }
+  let reports = suite::run(&turing_machine, &cases)?;
+  reports.iter().for_each(print_report);
+  let failed = reports.iter().filter(|x| !x.passed()).count();
+  println!(
+    "Summary: {} tests, {} passed, {} failed.",
+    reports.len(),
+    reports.len() - failed,
+    failed
+  );
+  if failed > 0 { Ok(ExitCode::FAILURE) } else { Ok(ExitCode::SUCCESS) }
+}
@@ -0,0 +1,26 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Tikz subcommand: LaTeX diagram of a Turing machine, or table of the configurations of a run.
+
+use crate::cli::TikzArgs;
+use crate::load_machine;
+use std::fs;
+use std::process::ExitCode;
+use turing_machine_simulator::{Error, tikz};
+
+/// Write the diagram of the machine, or the configurations of the run of the string.
+pub fn tikz(args: &TikzArgs) -> Result<ExitCode, Error> {
+  let tm = load_machine(&args.machine)?;
+  let latex = match &args.configurations {
+    | Some(string) => tikz::configurations(&tm, string)?,
+    | None => tikz::diagram(&tm),
+  };
+  match &args.output {
+    | Some(output) => fs::write(output, latex).map_err(|x| Error::WriteFile(x.to_string()))?,
+    | None => print!("{latex}"),
+  }
+  Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,298 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Tui subcommand: full-screen terminal visualizer of a run.
+
+use crate::cli::TuiArgs;
+use crate::load_machine;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use turing_machine_simulator::turing_machine::Execution;
+use turing_machine_simulator::turing_machine::transition::PatternTransition;
+use turing_machine_simulator::{Error, Outcome, Symbols, Tape};
+
+/// Available speeds, in steps per second.
+const SPEEDS: [u32; 9] = [1, 2, 5, 10, 20, 50, 100, 500, 1000];
+/// Minimum time between two frames while playing.
+const FRAME: Duration = Duration::from_millis(20);
+/// Width of each tape cell on screen.
+const CELL_WIDTH: u16 = 4;
+/// Help line shown at the bottom of the screen.
+const HELP: &str = "space play/pause | → step | ← back | +/- speed | r restart | q quit";
+
+/// State of the visualizer.
+struct App<'a> {
+  /// Run being visualized.
+  execution: Execution<'a>,
+  /// Whether the run advances on its own.
+  playing: bool,
+  /// Index of the current speed in SPEEDS.
+  speed: usize,
+  /// Instant of the last step done while playing.
+  last: Instant,
+  /// Instant the run started, for its timeout.
+  start: Instant,
+  /// Transition table of the machine, with the state of each transition.
+  table: Vec<(usize, PatternTransition)>,
+}
+
+impl<'a> App<'a> {
+  /// Creates the visualizer of the run, paused, at the index of the speed in SPEEDS.
+  fn new(execution: Execution<'a>, speed: usize) -> Self {
+    let table = execution.machine().pattern_table();
+    App { execution, playing: false, speed, last: Instant::now(), start: Instant::now(), table }
+  }
+
+  /// Returns the limit of the machine reached by the run, if any.
+  fn limit(&self) -> Option<Outcome> {
+    let limits = self.execution.machine().limits();
+    limits.exceeded(self.execution.steps(), &self.start, self.execution.tapes())
+  }
+
+  /// Returns true if the run can't advance anymore, because it halted or reached a limit.
+  fn finished(&self) -> bool {
+    self.execution.next_transition().is_none() || self.limit().is_some()
+  }
+
+  /// Do the steps due since the last one, stopping when the run finishes.
+  fn tick(&mut self) {
+    let interval = Duration::from_secs(1) / SPEEDS[self.speed];
+    while self.playing && self.last.elapsed() >= interval {
+      self.last += interval;
+      if self.finished() {
+        self.playing = false;
+      } else {
+        self.execution.step();
+      }
+    }
+  }
+
+  /// Returns how long to wait for an event before the next tick.
+  fn timeout(&self) -> Duration {
+    let interval = Duration::from_secs(1) / SPEEDS[self.speed];
+    if self.playing { interval.saturating_sub(self.last.elapsed()).max(FRAME) } else { FRAME * 50 }
+  }
+
+  /// Handle a key press. Returns false if the visualizer must exit.
+  fn key(&mut self, key: KeyCode) -> bool {
+    match key {
+      | KeyCode::Char('q') | KeyCode::Esc => return false,
+      | KeyCode::Char(' ') | KeyCode::Char('p') => {
+        self.playing = !self.playing && !self.finished();
+        self.last = Instant::now();
+      },
+      | KeyCode::Right | KeyCode::Char('s') => {
+        self.playing = false;
+        if !self.finished() {
+          self.execution.step();
+        }
+      },
+      | KeyCode::Left | KeyCode::Char('b') => {
+        self.playing = false;
+        self.execution.step_back();
+      },
+      | KeyCode::Char('+') | KeyCode::Up => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+      | KeyCode::Char('-') | KeyCode::Down => self.speed = self.speed.saturating_sub(1),
+      | KeyCode::Char('r') => {
+        self.playing = false;
+        self.start = Instant::now();
+        self.execution.restart();
+      },
+      | _ => {},
+    }
+    true
+  }
+
+  /// Returns the status of the run, shown in the header.
+  fn status(&self) -> String {
+    match (self.execution.outcome(), self.limit()) {
+      | (Some(outcome), _) => format!("halted: {outcome}"),
+      | (None, Some(limit)) => limit.to_string(),
+      | (None, None) if self.playing => "playing".to_string(),
+      | (None, None) => "paused".to_string(),
+    }
+  }
+
+  /// Draw the whole screen.
+  fn draw(&self, frame: &mut Frame) {
+    let ntapes = self.execution.tapes().len() as u16;
+    let [header, tapes, table, help] = Layout::vertical([
+      Constraint::Length(3),
+      Constraint::Length(3 * ntapes),
+      Constraint::Min(3),
+      Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    self.draw_header(frame, header);
+    let areas = Layout::vertical(vec![Constraint::Length(3); ntapes as usize]).split(tapes);
+    for (i, tape) in self.execution.tapes().iter().enumerate() {
+      draw_tape(frame, areas[i], i, tape, self.execution.machine().symbols());
+    }
+    self.draw_table(frame, table);
+    frame.render_widget(Paragraph::new(HELP).style(Style::new().fg(Color::DarkGray)), help);
+  }
+
+  /// Draw the current state, the steps done, the status and the speed.
+  fn draw_header(&self, frame: &mut Frame, area: Rect) {
+    let tm = self.execution.machine();
+    let state = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let line = Line::from(vec![
+      Span::raw("State "),
+      Span::styled(tm.state_name(self.execution.state()), state),
+      Span::raw(format!(
+        " | Step {} | {} | {} steps/s",
+        self.execution.steps(),
+        self.status(),
+        SPEEDS[self.speed]
+      )),
+    ]);
+    let title = format!(" \"{}\" ", self.execution.input());
+    frame.render_widget(Paragraph::new(line).block(Block::bordered().title(title)), area);
+  }
+
+  /// Returns the row of the transition about to fire, if any. The table is ordered so the first
+  /// one matching the state and the symbols readed is the one taken.
+  fn selected(&self) -> Option<usize> {
+    self.execution.next_transition()?;
+    let (state, read) = (self.execution.state(), self.execution.read());
+    self.table.iter().position(|(x, tr)| *x == state && tr.matches(&read))
+  }
+
+  /// Draw the transition table, highlighting the transition about to fire.
+  fn draw_table(&self, frame: &mut Frame, area: Rect) {
+    let tm = self.execution.machine();
+    let rows = self.table.iter().map(|(state, tr)| {
+      let read: String = tr.read_slice().iter().map(|x| x.name(tm.symbols())).collect();
+      let write: String = tr.write_slice().iter().map(|x| x.name(tm.symbols())).collect();
+      let direction: String = tr.move_slice().iter().map(|x| x.to_string()).collect();
+      Row::new(vec![tm.state_name(*state), read, tm.state_name(tr.next()), write, direction])
+    });
+    let widths = [Constraint::Fill(1); 5];
+    let table = Table::new(rows, widths)
+      .header(
+        Row::new(["From", "Read", "Next", "Write", "Move"])
+          .style(Style::new().add_modifier(Modifier::BOLD)),
+      )
+      .row_highlight_style(Style::new().fg(Color::Black).bg(Color::Yellow))
+      .block(Block::bordered().title(" Transitions "));
+    let mut state = TableState::new().with_selected(self.selected());
+    frame.render_stateful_widget(table, area, &mut state);
+  }
+}
+
+/// Draw a tape as a window of cells centred on its head.
+fn draw_tape(frame: &mut Frame, area: Rect, index: usize, tape: &Tape, symbols: &Symbols) {
+  let visible = (area.width.saturating_sub(2) / CELL_WIDTH).max(1) as isize;
+  let first = tape.head() - visible / 2;
+  let head = Style::new().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+  let cells: Vec<Span> = (first..first + visible)
+    .map(|pos| {
+      let cell = format!(" {} ", symbols.name(tape.cell(pos)));
+      if pos == tape.head() { Span::styled(cell, head) } else { Span::raw(cell) }
+    })
+    .flat_map(|x| [x, Span::raw("|")])
+    .collect();
+  let title = format!(" Tape {index} (head at {}) ", tape.head());
+  frame
+    .render_widget(Paragraph::new(Line::from(cells)).block(Block::bordered().title(title)), area);
+}
+
+/// Main loop of the visualizer: draw, wait for a key or the next tick, repeat.
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> std::io::Result<()> {
+  loop {
+    terminal.draw(|frame| app.draw(frame))?;
+    if event::poll(app.timeout())?
+      && let Event::Key(key) = event::read()?
+      && key.kind == KeyEventKind::Press
+      && !app.key(key.code)
+    {
+      return Ok(());
+    }
+    app.tick();
+  }
+}
+
+/// Run the visualizer on the string, until the user exits.
+pub fn tui(args: &TuiArgs) -> Result<ExitCode, Error> {
+  let turing_machine = load_machine(&args.machine)?;
+  turing_machine.check_input(&args.string)?;
+  let speed = SPEEDS.iter().position(|x| *x >= args.speed).unwrap_or(SPEEDS.len() - 1);
+  let mut app = App::new(turing_machine.execution(&args.string), speed);
+  let mut terminal = ratatui::init();
+  let result = event_loop(&mut terminal, &mut app);
+  ratatui::restore();
+  result.map_err(|x| Error::WriteFile(x.to_string()))?;
+  Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::commands::tui::App;
+  use ratatui::crossterm::event::KeyCode;
+  use turing_machine_simulator::{Limits, parser};
+
+  #[test]
+  fn test_keys() {
+    let tm = parser::from_file("example/example1.toml").unwrap();
+    let mut app = App::new(tm.execution("aa"), 0);
+    assert_eq!(app.status(), "paused");
+    assert!(app.key(KeyCode::Char(' ')));
+    assert_eq!(app.status(), "playing");
+    assert!(app.key(KeyCode::Right) && app.key(KeyCode::Right));
+    assert_eq!((app.execution.steps(), app.status().as_str()), (2, "paused"));
+    assert!(app.key(KeyCode::Left));
+    assert_eq!(app.execution.steps(), 1);
+    assert!(app.key(KeyCode::Char('+')));
+    assert_eq!(app.speed, 1);
+    for _ in 0..3 {
+      app.key(KeyCode::Right);
+    }
+    assert_eq!((app.execution.steps(), app.status().as_str()), (3, "halted: accepted"));
+    // Playing can't start once halted.
+    assert!(app.key(KeyCode::Char(' ')));
+    assert!(!app.playing);
+    assert!(app.key(KeyCode::Char('r')));
+    assert_eq!(app.execution.steps(), 0);
+    assert!(!app.key(KeyCode::Char('q')));
+  }
+
+  #[test]
+  fn test_status() {
+    let mut tm = parser::from_file("example/example1.toml").unwrap();
+    let app = App::new(tm.execution("b"), 0);
+    assert_eq!(app.status(), "halted: halted without a defined transition");
+    tm.set_limits(&Limits { steps: 1, ..Limits::default() });
+    let mut app = App::new(tm.execution("aa"), 0);
+    app.key(KeyCode::Right);
+    app.key(KeyCode::Right);
+    assert_eq!((app.execution.steps(), app.status().as_str()), (1, "reached the step limit"));
+    tm.set_limits(&Limits { cells: Some(2), ..Limits::default() });
+    let mut app = App::new(tm.execution("aa"), 0);
+    for _ in 0..3 {
+      app.key(KeyCode::Right);
+    }
+    assert!(app.finished());
+    assert_eq!((app.execution.steps(), app.status().as_str()), (2, "reached the tape-cell limit"));
+  }
+
+  #[test]
+  fn test_selected() {
+    let tm = parser::from_file("example/example2.toml").unwrap();
+    let mut app = App::new(tm.execution("aabcc"), 0);
+    while let Some(row) = app.selected() {
+      let (state, tr) = &app.table[row];
+      let taken = app.execution.next_transition().unwrap();
+      assert_eq!((*state, tr.next()), (app.execution.state(), taken.next()));
+      app.key(KeyCode::Right);
+    }
+    assert_eq!(app.status(), "halted: accepted");
+  }
+}
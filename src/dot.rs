@@ -0,0 +1,82 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! State diagram of a Turing machine in the DOT language of Graphviz.
+//!
+//! Each state is a node labelled with its name, with a double circle for the acceptance states,
+//! and an arrow from nowhere points to the initial state. Each transition is an edge labelled
+//! with `read → write, direction` for each tape, and the transitions between the same pair of
+//! states share a single edge, with a line per transition.
+
+use crate::turing_machine::TuringMachine;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Returns the text escaped to be written in a quoted DOT string.
+fn escape(text: &str) -> String {
+  text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns the DOT digraph of the states and transitions of the machine.
+pub fn to_string(tm: &TuringMachine) -> String {
+  let symbols = tm.symbols();
+  let mut dot = String::from("digraph {\n  rankdir=LR;\n  node [shape=circle];\n");
+  dot.push_str("  __start [shape=point];\n");
+  for state in 0..tm.nstates() {
+    let shape = if tm.acceptance().contains(&state) { ", shape=doublecircle" } else { "" };
+    writeln!(dot, "  q{state} [label=\"{}\"{shape}];", escape(&tm.state_name(state))).unwrap();
+  }
+  writeln!(dot, "  __start -> q{};", tm.initial()).unwrap();
+  // Label of each edge, a line per transition.
+  let mut edges: BTreeMap<(usize, usize), Vec<String>> = BTreeMap::new();
+  for (state, tr) in tm.pattern_table() {
+    let tapes: Vec<String> = (0..tr.len())
+      .map(|i| {
+        let (read, write) = (tr.read_slice()[i].name(symbols), tr.write_slice()[i].name(symbols));
+        format!("{read} → {write}, {}", tr.move_slice()[i])
+      })
+      .collect();
+    edges.entry((state, tr.next())).or_default().push(escape(&tapes.join(" | ")));
+  }
+  for ((from, next), lines) in edges {
+    writeln!(dot, "  q{from} -> q{next} [label=\"{}\"];", lines.join("\\n")).unwrap();
+  }
+  dot.push_str("}\n");
+  dot
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::dot::to_string;
+  use crate::parser;
+
+  #[test]
+  fn test_dot() {
+    let config = r#"
+      ntapes = 2
+      initial = "start"
+      accept = ["end"]
+      patterns = true
+      [[transition]]
+      from = "start"
+      next = "start"
+      read = "[ab]β"
+      write = "=\""
+      direction = "RS"
+      [[transition]]
+      from = "start"
+      next = "end"
+      read = "ββ"
+      write = "ββ"
+      direction = "LS"
+    "#;
+    let dot = to_string(&parser::from_str(config).unwrap());
+    assert!(dot.contains("  q1 [label=\"end\", shape=doublecircle];\n"));
+    assert!(dot.contains("  __start -> q0;\n"));
+    let parallel = "  q0 -> q0 [label=\"[ab] → =, R | β → \\\", S\"];\n";
+    assert!(dot.contains(parallel));
+    assert!(dot.contains("  q0 -> q1 [label=\"β → β, L | β → β, S\"];\n"));
+  }
+}
@@ -5,29 +5,103 @@
 //!
 //! Crate representing the Runtime errors.
 
-/// Enum representing the possible rutime errors.
+use core::fmt;
+use std::ops::Range;
 use thiserror::Error;
 
+/// Enum representing the possible rutime errors.
 #[derive(Clone, Debug, PartialEq, Eq, Error)]
 pub enum Error {
-  #[error("Multiple transitions for the same pair state-readed.")]
-  Indeterminancy,
-  #[error("Run stopped, reached the maximum ammount of steps.")]
-  MaxStepsReached,
-  #[error("The number of tapes doesn't coincide with the transition ({0}, {1}).)")]
+  #[error("Multiple transitions for the same pair state-readed ({0}, {1}).")]
+  Indeterminancy(String, String),
+  #[error("The number of tapes doesn't coincide with the transition ({0}, {1}).")]
   UnmatchingSizes(usize, usize),
   #[error("The number of tapes must be atleast one.")]
   TapeErrorCount,
+  #[error("The tape {0} doesn't exist, the machine has {1} tapes.")]
+  UnknownTape(usize, usize),
   #[error("The direction and write vectors size doesn't match ({0}, {1}).")]
   TransitionSizeUnmatch(usize, usize),
   #[error("Found an unkown direction while parsing: {0}.")]
   UnkownDirection(String),
+  #[error("The symbol {0} is not in the tape alphabet.")]
+  UnknownSymbol(String),
+  #[error("The input \"{0}\" contains the symbol {1}, which is not in the input alphabet.")]
+  InvalidInput(String, String),
+  #[error("The input \"{0}\" contains the character {1:?}, reserved to the longer symbols.")]
+  ReservedInput(String, char),
+  #[error("The state {0} is written both as an integer and as a string.")]
+  MixedState(String),
+  #[error("Found an invalid symbol while parsing: \"{0}\".")]
+  InvalidSymbol(String),
+  #[error("Too many symbols of more than one character, the maximum is {0}.")]
+  SymbolLimit(usize),
+  #[error("Found an invalid pattern while parsing: {0}.")]
+  InvalidPattern(String),
+  #[error("Found an unkown outcome while parsing: {0}.")]
+  UnknownOutcome(String),
   #[error("Found an error parsing the toml file: {0}.")]
-  ErrorParsing(String),
+  Parsing(String),
+  #[error("Couldn't import the machine: {0}.")]
+  Import(String),
+  #[error("Couldn't export the machine: {0}.")]
+  Export(String),
   #[error("Couldn't open the toml file: {0}.")]
-  ErrorOpenFile(String),
-  #[error("Failed the test: {0}.")]
-  Fail(String),
+  OpenFile(String),
   #[error("Error writting in file: {0}")]
-  ErrorWriteFile(String),
+  WriteFile(String),
+  #[error("Found {} errors in the configuration.", .0.len())]
+  Invalid(Vec<Located>),
+}
+
+/// An error located in the source of a configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Located {
+  /// Error found.
+  pub error: Error,
+  /// Index of the transition where it was found, if any.
+  pub transition: Option<usize>,
+  /// Byte range of the source where it was found, if known.
+  pub span: Option<Range<usize>>,
+}
+
+impl fmt::Display for Located {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.transition {
+      | Some(x) => write!(f, "transition {x}: {}", self.error),
+      | None => write!(f, "{}", self.error),
+    }
+  }
+}
+
+impl Located {
+  /// Returns the error followed by a snippet of the source pointing where it is located.
+  pub fn render(&self, path: &str, source: &str) -> String {
+    match &self.span {
+      | Some(span) => format!("error: {self}\n{}", snippet(path, source, span)),
+      | None => format!("error: {self}\n --> {path}"),
+    }
+  }
+}
+
+/// Returns the line and column, starting at 1, of a byte offset of the source.
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+  let before = &source[..offset.min(source.len())];
+  let line = before.matches('\n').count() + 1;
+  let column = before.chars().rev().take_while(|x| *x != '\n').count() + 1;
+  (line, column)
+}
+
+/// Returns a snippet of the source with the first line of the span, and a caret under it.
+pub fn snippet(path: &str, source: &str, span: &Range<usize>) -> String {
+  let (line, column) = line_col(source, span.start);
+  let text = source.lines().nth(line - 1).unwrap_or_default();
+  let width =
+    source.get(span.clone()).map(|x| x.lines().next().unwrap_or_default().chars().count());
+  let carets = "^".repeat(width.unwrap_or_default().max(1));
+  let margin = " ".repeat(line.to_string().len());
+  let padding = " ".repeat(column - 1);
+  format!(
+    "{margin}--> {path}:{line}:{column}\n{margin} |\n{line} | {text}\n{margin} | {padding}{carets}"
+  )
 }
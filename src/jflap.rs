@@ -0,0 +1,461 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Import and export of Turing machines as JFLAP files.
+//!
+//! JFLAP stores its machines as XML, in `.jff` files. Single-tape and multi-tape Turing machines
+//! are converted to a [`RawTuringMachine`], with the same states and transitions, so
+//! [`parser::parse`](crate::parser::parse) checks them as any other configuration. Each field keeps
+//! the location of its element in the XML, so the errors point to the right place.
+//!
+//! The blank, empty in the file, is named `□` as JFLAP prints it, and the `~` wildcard, which
+//! reads any symbol or writes back the one read, is converted to `*` and `=`. Building blocks
+//! can't be represented, so machines with them are rejected. Anything else that is lost is
+//! reported as a warning.
+//!
+//! The export goes the other way, from a parsed [`TuringMachine`]. The classes are expanded into
+//! one transition per symbol, and `*` is written as `~` unless another transition of the state
+//! reads some of the same symbols, as JFLAP would take both; it is expanded over the symbols of the
+//! machine then. JFLAP halts as soon as a final state is entered, so the transitions leaving the
+//! acceptance states are left out. It has no rejection states, but halting in a state that isn't
+//! final rejects the string, so they are exported as states that aren't final without their
+//! transitions, and reported as warnings. Limits and the output tape are left out. The states are
+//! laid out in columns by their distance from the initial state, so the diagram is readable when
+//! opened.
+
+use crate::error::Error;
+use crate::layout;
+use crate::parser::{Import, RawState, RawSymbols, RawTransition, RawTuringMachine};
+use crate::pattern::{self, Pattern, WritePattern};
+use crate::turing_machine::transition::PatternTransition;
+use crate::turing_machine::{BLANK, TuringMachine};
+use roxmltree::{Document, Node};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::ops::Range;
+use toml::Spanned;
+
+/// Name of the blank in JFLAP.
+pub const JFLAP_BLANK: &str = "□";
+/// Symbol of JFLAP that reads any symbol, or writes back the one read.
+const WILDCARD: &str = "~";
+
+/// Returns the text of the first child element with the given name, if any.
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+  node.children().find(|x| x.has_tag_name(name)).map(|x| x.text().unwrap_or_default())
+}
+
+/// Returns an import error, with the location of the element where it was found.
+fn error(node: Node, message: String) -> Error {
+  let position = node.document().text_pos_at(node.range().start);
+  Error::Import(format!("{message} (line {}, column {})", position.row, position.col))
+}
+
+/// Returns the symbol of a tape as written in a configuration string: the blank if empty, and
+/// the characters with a meaning in the patterns escaped.
+fn symbol(text: &str, write: bool, node: Node) -> Result<String, Error> {
+  let mut chars = text.chars();
+  match (chars.next(), chars.next()) {
+    | (None, _) => Ok(JFLAP_BLANK.to_string()),
+    | _ if text == WILDCARD => Ok(if write { "=" } else { "*" }.to_string()),
+    | (Some(x), None) => Ok(pattern::escape(x)),
+    | _ => Err(error(node, format!("the symbol \"{text}\" has more than one character"))),
+  }
+}
+
+/// Content and element of each tape of a field of a transition, with the range of the elements.
+type Fields<'a> = (Vec<(&'a str, Node<'a, 'a>)>, Range<usize>);
+
+/// Returns the content of each tape of a field of the transition, with the range of the
+/// elements. Single-tape machines have a single element without the tape attribute.
+fn tape_fields<'a>(node: Node<'a, '_>, name: &str, ntapes: usize) -> Result<Fields<'a>, Error> {
+  let mut fields = vec![None; ntapes];
+  let mut range: Option<Range<usize>> = None;
+  for x in node.children().filter(|x| x.has_tag_name(name)) {
+    let tape = match x.attribute("tape") {
+      | Some(tape) => tape.parse::<usize>().ok().filter(|x| (1..=ntapes).contains(x)),
+      | None if ntapes == 1 => Some(1),
+      | None => None,
+    };
+    let Some(tape) = tape else { return Err(error(x, format!("the {name} has no valid tape"))) };
+    fields[tape - 1] = Some((x.text().unwrap_or_default(), x));
+    let r = x.range();
+    range = Some(range.map_or(r.clone(), |y| y.start.min(r.start)..y.end.max(r.end)));
+  }
+  let fields: Option<Vec<_>> = fields.into_iter().collect();
+  match (fields, range) {
+    | (Some(fields), Some(range)) => Ok((fields, range)),
+    | _ => Err(error(node, format!("the transition has no {name} for some tape"))),
+  }
+}
+
+/// Returns the transition of an element, given the name of each state id.
+fn transition(
+  node: Node, ntapes: usize, names: &HashMap<&str, String>,
+) -> Result<Spanned<RawTransition>, Error> {
+  let state = |field: &str| {
+    let id = child_text(node, field).unwrap_or_default().trim();
+    match names.get(id) {
+      | Some(x) => Ok(RawState::Name(x.clone())),
+      | None => Err(error(node, format!("the transition goes {field} the unknown state \"{id}\""))),
+    }
+  };
+  let (from, next) = (state("from")?, state("to")?);
+  let (read, read_range) = tape_fields(node, "read", ntapes)?;
+  let (write, write_range) = tape_fields(node, "write", ntapes)?;
+  let (moves, move_range) = tape_fields(node, "move", ntapes)?;
+  let read: Result<String, Error> = read.iter().map(|(x, node)| symbol(x, false, *node)).collect();
+  let write: Result<String, Error> = write.iter().map(|(x, node)| symbol(x, true, *node)).collect();
+  let direction: String = moves.iter().map(|x| x.0.trim()).collect();
+  let transition = RawTransition {
+    from,
+    next,
+    read: Spanned::new(read_range, RawSymbols::Chars(read?)),
+    write: Spanned::new(write_range, RawSymbols::Chars(write?)),
+    direction: Spanned::new(move_range, direction),
+  };
+  Ok(Spanned::new(node.range(), transition))
+}
+
+/// Import the Turing machine of the content of a JFLAP file.
+pub fn from_str(xml: &str) -> Result<Import, Error> {
+  let document = Document::parse(xml).map_err(|x| Error::Import(x.to_string()))?;
+  let structure = document.root_element();
+  let kind = child_text(structure, "type").unwrap_or_default().trim();
+  match kind {
+    | "turing" => {},
+    | "turingbb" => return Err(error(structure, "building blocks aren't supported".to_string())),
+    | x => {
+      return Err(error(structure, format!("the file has a {x} automaton, not a Turing machine")));
+    },
+  }
+  let mut warnings = Vec::new();
+  let ntapes_node = structure.children().find(|x| x.has_tag_name("tapes"));
+  let ntapes = match ntapes_node {
+    | Some(x) => match x.text().unwrap_or_default().trim().parse::<usize>() {
+      | Ok(n) => Spanned::new(x.range(), n),
+      | Err(_) => return Err(error(x, "the number of tapes isn't a number".to_string())),
+    },
+    | None => Spanned::new(structure.range(), 1),
+  };
+  let automaton = structure
+    .children()
+    .find(|x| x.has_tag_name("automaton"))
+    .ok_or_else(|| error(structure, "the file has no automaton".to_string()))?;
+  if let Some(x) = automaton.children().find(|x| x.has_tag_name("block")) {
+    return Err(error(x, "building blocks aren't supported".to_string()));
+  }
+  if automaton.children().any(|x| x.has_tag_name("note")) {
+    warnings.push("the notes of the automaton are left out".to_string());
+  }
+  // States, named after JFLAP unless the names are repeated.
+  let states: Vec<Node> = automaton.children().filter(|x| x.has_tag_name("state")).collect();
+  let mut seen = HashSet::new();
+  let unique = states.iter().all(|x| seen.insert(x.attribute("name")));
+  if !unique {
+    warnings.push("the state names are repeated, so the states are named by their ids".to_string());
+  }
+  let mut names = HashMap::new();
+  for x in &states {
+    let id = x.attribute("id").ok_or_else(|| error(*x, "the state has no id".to_string()))?;
+    let name = match x.attribute("name") {
+      | Some(name) if unique => name.to_string(),
+      | _ => format!("q{id}"),
+    };
+    names.insert(id, name);
+  }
+  let has = |x: &Node, tag: &str| x.children().any(|y| y.has_tag_name(tag));
+  let name = |x: &Node| names.get(x.attribute("id").unwrap()).unwrap().clone();
+  let initial = match states.iter().find(|x| has(x, "initial")) {
+    | Some(x) => RawState::Name(name(x)),
+    | None => return Err(error(automaton, "the automaton has no initial state".to_string())),
+  };
+  let accept = states.iter().filter(|x| has(x, "final")).map(|x| RawState::Name(name(x))).collect();
+  let transition: Vec<Spanned<RawTransition>> = automaton
+    .children()
+    .filter(|x| x.has_tag_name("transition"))
+    .map(|x| transition(x, *ntapes.get_ref(), &names))
+    .collect::<Result<_, _>>()?;
+  // JFLAP allows several transitions for the same state and symbols read.
+  let mut keys = HashSet::new();
+  let nondeterministic = !transition
+    .iter()
+    .all(|x| keys.insert((x.get_ref().from.name(), x.get_ref().read.get_ref().clone())));
+  let machine = RawTuringMachine {
+    ntapes,
+    initial,
+    accept,
+    reject: Vec::new(),
+    nondeterministic,
+    budget: None,
+    output: None,
+    blank: Some(Spanned::new(structure.range(), JFLAP_BLANK.to_string())),
+    blank_glyph: None,
+    input_alphabet: None,
+    tape_alphabet: None,
+    max_steps: None,
+    timeout: None,
+    max_cells: None,
+    detect_cycles: false,
+    patterns: true,
+    transition,
+  };
+  Ok(Import { machine, warnings })
+}
+
+/// Returns the text escaped to be written in XML.
+fn escape(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for x in text.chars() {
+    match x {
+      | '&' => escaped.push_str("&amp;"),
+      | '<' => escaped.push_str("&lt;"),
+      | '>' => escaped.push_str("&gt;"),
+      | '"' => escaped.push_str("&quot;"),
+      | x => escaped.push(x),
+    }
+  }
+  escaped
+}
+
+/// Returns the symbol as written in a JFLAP file: empty for the blank.
+fn export_symbol(tm: &TuringMachine, x: char) -> Result<String, Error> {
+  let name = tm.symbols().name(x);
+  match x {
+    | BLANK => Ok(String::new()),
+    | _ if name == WILDCARD => {
+      Err(Error::Export("the symbol ~ is the wildcard of JFLAP".to_string()))
+    },
+    | _ if name.chars().count() > 1 => {
+      Err(Error::Export(format!("the symbol \"{name}\" has more than one character")))
+    },
+    | _ => Ok(escape(&name)),
+  }
+}
+
+/// Returns the symbols read by `*`, when it is expanded: the blank and the tape alphabet if
+/// declared, or the input alphabet and every symbol in the transitions otherwise.
+fn known_symbols(tm: &TuringMachine, table: &[(usize, PatternTransition)]) -> Vec<char> {
+  let mut symbols = BTreeSet::from([BLANK]);
+  if let Some(alphabet) = tm.tape_alphabet() {
+    symbols.extend(alphabet);
+    return symbols.into_iter().collect();
+  }
+  symbols.extend(tm.input_alphabet().into_iter().flatten());
+  for (_, tr) in table {
+    symbols.extend(tr.read_slice().iter().flat_map(|x| x.symbols()));
+    symbols.extend(tr.write_slice().iter().filter_map(|x| match x {
+      | WritePattern::Symbol(x) => Some(*x),
+      | WritePattern::Same => None,
+    }));
+  }
+  symbols.into_iter().collect()
+}
+
+/// Returns the symbols read in each tape by the JFLAP transitions of a transition of the state,
+/// None being the `~` wildcard. Only the symbols where it is the transition taken are kept,
+/// unless the machine is nondeterministic.
+fn expand(
+  tm: &TuringMachine, table: &[(usize, PatternTransition)], known: &[char], state: usize,
+  tr: &PatternTransition,
+) -> Vec<Vec<Option<char>>> {
+  let overlaps = table.iter().any(|(s, x)| *s == state && x != tr && x.overlap(tr).is_some());
+  let mut combinations: Vec<Vec<Option<char>>> = vec![Vec::new()];
+  for x in tr.read_slice() {
+    let symbols: Vec<Option<char>> = match x {
+      | Pattern::Any if !overlaps => vec![None],
+      | Pattern::Any => known.iter().copied().map(Some).collect(),
+      | x => x.symbols().iter().copied().map(Some).collect(),
+    };
+    combinations = combinations
+      .into_iter()
+      .flat_map(|x| symbols.iter().map(move |y| [x.as_slice(), &[*y]].concat()))
+      .collect();
+  }
+  combinations.retain(|x| {
+    let Some(read) = x.iter().copied().collect::<Option<Vec<char>>>() else { return true };
+    let taken = table.iter().find(|(s, y)| *s == state && y.matches(&read));
+    tm.is_nondeterministic() || taken.is_some_and(|(_, y)| y == tr)
+  });
+  combinations
+}
+
+/// Returns the symbol read or written as written in a JFLAP file, None being the wildcard.
+fn export_pattern(tm: &TuringMachine, x: Option<char>) -> Result<String, Error> {
+  match x {
+    | Some(x) => export_symbol(tm, x),
+    | None => Ok(WILDCARD.to_string()),
+  }
+}
+
+/// Returns what the export changes in the machine, to be reported as warnings.
+pub fn warnings(tm: &TuringMachine) -> Vec<String> {
+  let mut reject: Vec<usize> = tm.rejection().iter().copied().collect();
+  reject.sort();
+  reject
+    .into_iter()
+    .map(|x| {
+      let name = tm.state_name(x);
+      format!("the rejection state {name} is exported as a state that isn't final, without its transitions")
+    })
+    .collect()
+}
+
+/// Returns the content of a JFLAP file with the Turing machine.
+pub fn to_string(tm: &TuringMachine) -> Result<String, Error> {
+  // The final states halt the run, so their transitions are never taken.
+  let table: Vec<(usize, PatternTransition)> =
+    tm.pattern_table().into_iter().filter(|x| tm.final_outcome(x.0).is_none()).collect();
+  let known = known_symbols(tm, &table);
+  let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+  xml.push_str("<structure>\n  <type>turing</type>\n");
+  if tm.ntapes() > 1 {
+    writeln!(xml, "  <tapes>{}</tapes>", tm.ntapes()).unwrap();
+  }
+  xml.push_str("  <automaton>\n");
+  for (state, (x, y)) in layout::positions(tm).into_iter().enumerate() {
+    let name = escape(&tm.state_name(state));
+    writeln!(xml, "    <state id=\"{state}\" name=\"{name}\">").unwrap();
+    writeln!(xml, "      <x>{x}.0</x>\n      <y>{y}.0</y>").unwrap();
+    if state == tm.initial() {
+      xml.push_str("      <initial/>\n");
+    }
+    if tm.acceptance().contains(&state) {
+      xml.push_str("      <final/>\n");
+    }
+    xml.push_str("    </state>\n");
+  }
+  // Single-tape machines have a single element per field, without the tape attribute.
+  let tape =
+    |i: usize| if tm.ntapes() > 1 { format!(" tape=\"{}\"", i + 1) } else { String::new() };
+  for (state, tr) in &table {
+    for read in expand(tm, &table, &known, *state, tr) {
+      writeln!(xml, "    <transition>\n      <from>{state}</from>\n      <to>{}</to>", tr.next())
+        .unwrap();
+      for (i, x) in read.iter().enumerate() {
+        writeln!(xml, "      <read{}>{}</read>", tape(i), export_pattern(tm, *x)?).unwrap();
+      }
+      for (i, (x, y)) in tr.write_slice().iter().zip(&read).enumerate() {
+        let write = match x {
+          | WritePattern::Symbol(x) => Some(*x),
+          | WritePattern::Same => *y,
+        };
+        writeln!(xml, "      <write{}>{}</write>", tape(i), export_pattern(tm, write)?).unwrap();
+      }
+      for (i, x) in tr.move_slice().iter().enumerate() {
+        writeln!(xml, "      <move{}>{x}</move>", tape(i)).unwrap();
+      }
+      xml.push_str("    </transition>\n");
+    }
+  }
+  xml.push_str("  </automaton>\n</structure>\n");
+  Ok(xml)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::jflap::{from_str, to_string, warnings};
+  use crate::parser::{self, to_toml};
+  use crate::turing_machine::Outcome;
+
+  /// Machine that accepts the strings of a's and b's ending in b.
+  const MACHINE: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<structure>
+  <type>turing</type>
+  <automaton>
+    <state id="0" name="q0"><x>60.0</x><y>80.0</y><initial/></state>
+    <state id="1" name="q1"><x>180.0</x><y>80.0</y></state>
+    <state id="2" name="q2"><x>300.0</x><y>80.0</y><final/></state>
+    <state id="3" name="q3"><x>180.0</x><y>200.0</y></state>
+    <transition><from>0</from><to>0</to><read>~</read><write>~</write><move>R</move></transition>
+    <transition><from>0</from><to>1</to><read/><write/><move>L</move></transition>
+    <transition><from>1</from><to>2</to><read>b</read><write>b</write><move>S</move></transition>
+    <transition><from>1</from><to>3</to><read>a</read><write>a</write><move>S</move></transition>
+    <note><text>ends in b</text></note>
+  </automaton>
+</structure>"#;
+
+  #[test]
+  fn test_import() {
+    let import = from_str(MACHINE).unwrap();
+    assert_eq!(import.warnings[0], "the notes of the automaton are left out");
+    let tm = parser::parse(&import.machine).unwrap();
+    assert_eq!(tm.state_name(tm.initial()), "q0");
+    assert_eq!(tm.run("aab", None), Ok(Outcome::Accepted));
+    assert_eq!(tm.run("aba", None), Ok(Outcome::HaltedUndefined));
+    // The written configuration is the same machine.
+    let toml = to_toml(&import.machine).unwrap();
+    let tm = parser::from_str(&toml).unwrap();
+    assert_eq!(tm.run("ab", None), Ok(Outcome::Accepted));
+    assert!(toml.contains("read = \"□\""));
+    let multitape = MACHINE
+      .replace("<type>turing</type>", "<type>turing</type><tapes>2</tapes>")
+      .replace("<read>b</read>", "<read tape=\"1\">b</read><read tape=\"2\"/>");
+    let Err(Error::Import(x)) = from_str(&multitape) else { panic!("expected an import error") };
+    assert!(x.starts_with("the read has no valid tape"));
+    let blocks = MACHINE.replace("<type>turing</type>", "<type>turingbb</type>");
+    assert!(matches!(from_str(&blocks), Err(Error::Import(_))));
+  }
+
+  #[test]
+  fn test_export() {
+    let tm = parser::parse(&from_str(MACHINE).unwrap().machine).unwrap();
+    let xml = to_string(&tm).unwrap();
+    assert!(xml.contains("<state id=\"0\" name=\"q0\">\n      <x>100.0</x>\n      <y>100.0</y>"));
+    assert!(xml.contains("<read></read>"));
+    // The `~` of the first state is expanded, as the blank is read by another transition.
+    assert!(!xml.contains('~') && xml.contains("<read>b</read>\n      <write>b</write>"));
+    // The exported file is the same machine.
+    let import = from_str(&xml).unwrap();
+    assert!(import.warnings.is_empty());
+    let exported = parser::parse(&import.machine).unwrap();
+    for x in ["aab", "aba", "", "b"] {
+      assert_eq!(exported.run(x, None), tm.run(x, None));
+    }
+    let config = r#"
+      ntapes = 2
+      initial = "start"
+      accept = ["end"]
+      reject = ["fail"]
+      patterns = true
+      [[transition]]
+      from = "start"
+      next = "end"
+      read = "a*"
+      write = "=b"
+      direction = "RS"
+      [[transition]]
+      from = "start"
+      next = "fail"
+      read = "[bc]β"
+      write = "=β"
+      direction = "SS"
+      [[transition]]
+      from = "fail"
+      next = "end"
+      read = "*β"
+      write = "=β"
+      direction = "SS"
+    "#;
+    let tm = parser::from_str(config).unwrap();
+    let xml = to_string(&tm).unwrap();
+    assert!(xml.contains("<tapes>2</tapes>") && xml.contains("<read tape=\"2\">~</read>"));
+    // The class is expanded, and the transitions of the rejection state are left out.
+    assert!(xml.contains("<read tape=\"1\">c</read>") && !xml.contains("<from>2</from>"));
+    assert_eq!(
+      warnings(&tm),
+      vec![
+        "the rejection state fail is exported as a state that isn't final, without its transitions"
+      ]
+    );
+    let exported = parser::parse(&from_str(&xml).unwrap().machine).unwrap();
+    assert_eq!(exported.run("a", None), Ok(Outcome::Accepted));
+    assert_eq!(exported.run("c", None), Ok(Outcome::HaltedUndefined));
+    let symbols = config.replace("\"a*\"", "[\"a\", \"X1\"]");
+    let message = "the symbol \"X1\" has more than one character".to_string();
+    assert_eq!(to_string(&parser::from_str(&symbols).unwrap()), Err(Error::Export(message)));
+  }
+}
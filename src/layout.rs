@@ -0,0 +1,58 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Layout of the state diagram, shared by the exports that place the states themselves.
+
+use crate::turing_machine::TuringMachine;
+use std::collections::VecDeque;
+
+/// Horizontal distance between the columns of states, in pixels.
+const COLUMN: usize = 150;
+/// Vertical distance between the states of a column, in pixels.
+const ROW: usize = 120;
+
+/// Returns the position of each state in the diagram, in pixels. The states are placed in columns
+/// by their distance from the initial state, and the unreachable ones in a last column.
+pub(crate) fn positions(tm: &TuringMachine) -> Vec<(usize, usize)> {
+  let nstates = tm.nstates();
+  let mut next = vec![Vec::new(); nstates];
+  for (state, tr) in tm.pattern_table() {
+    next[state].push(tr.next());
+  }
+  let mut column = vec![None; nstates];
+  column[tm.initial()] = Some(0);
+  let mut queue = VecDeque::from([tm.initial()]);
+  while let Some(state) = queue.pop_front() {
+    let depth = column[state].unwrap();
+    for x in &next[state] {
+      if column[*x].is_none() {
+        column[*x] = Some(depth + 1);
+        queue.push_back(*x);
+      }
+    }
+  }
+  let last = column.iter().flatten().max().map_or(0, |x| x + 1);
+  let mut rows = vec![0; last + 1];
+  let mut positions = Vec::new();
+  for x in column {
+    let x = x.unwrap_or(last);
+    positions.push((COLUMN * x + 100, ROW * rows[x] + 100));
+    rows[x] += 1;
+  }
+  positions
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::layout::positions;
+  use crate::parser;
+
+  #[test]
+  fn test_positions() {
+    let tm = parser::from_file("example/example1.toml").unwrap();
+    // The states 1 and 2 are one step away from 0, and 3 is two.
+    assert_eq!(positions(&tm), vec![(100, 100), (250, 100), (250, 220), (400, 100)]);
+  }
+}
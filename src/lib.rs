@@ -0,0 +1,49 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Multitape and deterministic Turing machine simulator.
+//!
+//! A [`TuringMachine`] can be built by hand, using [`TuringMachine::new`] and
+//! [`TuringMachine::insert_transition`], or loaded from a toml configuration with
+//! [`parser::from_str`] or [`parser::from_file`].
+//!
+//! ```
+//! use turing_machine_simulator::{Outcome, parser};
+//!
+//! let config = r#"
+//!   ntapes = 1
+//!   initial = 0
+//!   accept = [1]
+//!
+//!   [[transition]]
+//!   from = 0
+//!   next = 1
+//!   read = "a"
+//!   write = "a"
+//!   direction = "S"
+//! "#;
+//! let tm = parser::from_str(config).unwrap();
+//! assert_eq!(tm.run("a", None), Ok(Outcome::Accepted));
+//! assert_eq!(tm.run("b", None), Ok(Outcome::HaltedUndefined));
+//! ```
+
+pub mod dot;
+pub mod error;
+pub mod jflap;
+mod layout;
+pub mod lint;
+pub mod morphett;
+pub mod parser;
+pub mod pattern;
+pub mod replay;
+pub mod suite;
+pub mod tikz;
+pub mod turing_machine;
+pub mod turingmachine_io;
+
+pub use crate::error::Error;
+pub use crate::turing_machine::tape::{Tape, TapeOutput};
+pub use crate::turing_machine::transition::{Direction, Transition};
+pub use crate::turing_machine::{Limits, Outcome, Symbols, TuringMachine};
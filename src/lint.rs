@@ -0,0 +1,332 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Static analysis of a Turing machine configuration, without running it.
+//!
+//! Unlike [`parser::parse`](crate::parser::parse), which only reports the errors preventing the
+//! machine from being loaded, every likely mistake is also reported, each one with the part of
+//! the configuration where it is located.
+
+use crate::error::Error;
+use crate::parser::{RawTuringMachine, convert_direction};
+use crate::pattern::{Pattern, WritePattern};
+use crate::turing_machine::Symbols;
+use core::fmt;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::Range;
+
+/// Severity of a problem found in a configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+  /// The configuration can't be loaded.
+  Error,
+  /// The configuration can be loaded, but it is probably wrong.
+  Warning,
+}
+
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      | Severity::Error => write!(f, "error"),
+      | Severity::Warning => write!(f, "warning"),
+    }
+  }
+}
+
+/// A problem found in a configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+  /// Severity of the problem.
+  pub severity: Severity,
+  /// Part of the configuration where the problem is located.
+  pub location: String,
+  /// Byte range of the source where the problem is located, if it is a single place.
+  pub span: Option<Range<usize>>,
+  /// Description of the problem.
+  pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+  }
+}
+
+/// Returns the description of an error found parsing the symbols of a transition.
+fn describe(error: &Error) -> String {
+  match error {
+    | Error::InvalidPattern(x) => format!("invalid pattern {x}"),
+    | Error::InvalidSymbol(x) => format!("invalid symbol \"{x}\""),
+    | x => x.to_string(),
+  }
+}
+
+/// Struct collecting the problems found.
+#[derive(Default)]
+struct Linter {
+  diagnostics: Vec<Diagnostic>,
+  /// Names of the symbols longer than a single character found.
+  symbols: Symbols,
+}
+
+impl Linter {
+  /// Returns the patterns as a string, as they are written in the configuration.
+  fn patterns(&self, x: &[Pattern]) -> String {
+    x.iter().map(|x| x.name(&self.symbols)).collect()
+  }
+
+  /// Add a problem to the report.
+  fn report(
+    &mut self, severity: Severity, location: &str, span: Option<Range<usize>>, message: String,
+  ) {
+    let location = location.to_string();
+    self.diagnostics.push(Diagnostic { severity, location, span, message });
+  }
+
+  /// Check the number of tapes, the output tape and the blank.
+  fn check_tapes(&mut self, rtm: &RawTuringMachine) {
+    let ntapes = *rtm.ntapes.get_ref();
+    if ntapes == 0 {
+      let message = "the number of tapes must be atleast one".to_string();
+      self.report(Severity::Error, "ntapes", Some(rtm.ntapes.span()), message);
+    }
+    if let Some(output) = rtm.output.as_ref().filter(|x| *x.get_ref() >= ntapes) {
+      let message = format!("the output tape {output} doesn't exist, there are {ntapes} tapes");
+      self.report(Severity::Error, "output", Some(output.span()), message);
+    }
+    if let Some(blank) = &rtm.blank
+      && self.symbols.set_blank(blank.get_ref(), rtm.blank_glyph.as_deref()).is_err()
+    {
+      let message = "the blank symbol can't be empty".to_string();
+      self.report(Severity::Error, "blank", Some(blank.span()), message);
+    }
+  }
+
+  /// Check the size and the directions of each transition, and the duplicated ones.
+  fn check_transitions(&mut self, rtm: &RawTuringMachine) {
+    let ntapes = *rtm.ntapes.get_ref();
+    let mut seen: HashMap<(String, Vec<Pattern>), usize> = HashMap::new();
+    for (i, tr) in rtm.transition.iter().map(|x| x.get_ref()).enumerate() {
+      let location = format!("transition {i} ({})", tr.from.name());
+      let read = tr.read.get_ref().read(&mut self.symbols, rtm.patterns);
+      let write = tr.write.get_ref().write(&mut self.symbols, rtm.patterns);
+      let errors = [(&tr.read, read.as_ref().err()), (&tr.write, write.as_ref().err())];
+      for (field, error) in errors {
+        if let Some(error) = error {
+          self.report(Severity::Error, &location, Some(field.span()), describe(error));
+        }
+      }
+      let fields = [
+        ("reads", "symbols", tr.read.span(), read.as_ref().map(|x| x.len())),
+        ("writes", "symbols", tr.write.span(), write.as_ref().map(|x| x.len())),
+        ("has", "directions", tr.direction.span(), Ok(tr.direction.get_ref().chars().count())),
+      ];
+      for (verb, noun, span, len) in fields {
+        if let Ok(len) = len
+          && len != ntapes
+        {
+          let message = format!("{verb} {len} {noun}, but there are {ntapes} tapes");
+          self.report(Severity::Error, &location, Some(span), message);
+        }
+      }
+      for x in tr.direction.get_ref().chars().filter(|x| convert_direction(*x).is_err()) {
+        let message = format!("unknown direction {x}");
+        self.report(Severity::Error, &location, Some(tr.direction.span()), message);
+      }
+      let Ok(read) = read else { continue };
+      let key = (tr.from.name(), read.clone());
+      if let Some(first) = seen.get(&key) {
+        let other = rtm.transition.get(*first).unwrap().get_ref();
+        let same =
+          (&other.next, &other.write, &other.direction) == (&tr.next, &tr.write, &tr.direction);
+        let (severity, kind) = match (rtm.nondeterministic, same) {
+          | (_, true) => (Severity::Warning, "duplicates"),
+          | (false, false) => (Severity::Error, "is nondeterministic with"),
+          | (true, false) => continue,
+        };
+        let message = format!("{kind} transition {first}, reading [{}]", self.patterns(&read));
+        self.report(severity, &location, Some(tr.read.span()), message);
+      } else {
+        seen.insert(key, i);
+      }
+    }
+  }
+
+  /// Check the states: the initial one, the unreachable ones, the final states with outgoing
+  /// transitions and the states without them.
+  fn check_states(&mut self, rtm: &RawTuringMachine) {
+    let initial = rtm.initial.name();
+    let accept: BTreeSet<String> = rtm.accept.iter().map(|x| x.name()).collect();
+    let reject: BTreeSet<String> = rtm.reject.iter().map(|x| x.name()).collect();
+    let mut edges: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for tr in rtm.transition.iter().map(|x| x.get_ref()) {
+      edges.entry(tr.from.name()).or_default().insert(tr.next.name());
+    }
+    let is_final = |x: &String| accept.contains(x) || reject.contains(x);
+    if !edges.contains_key(&initial) && !is_final(&initial) {
+      let message = format!("the initial state {initial} isn't final and has no transitions");
+      self.report(Severity::Error, "initial", None, message);
+    }
+    for x in accept.intersection(&reject) {
+      let message = format!("the state {x} is also an acceptance state");
+      self.report(Severity::Error, "reject", None, message);
+    }
+    for (key, set) in [("accept", &accept), ("reject", &reject)] {
+      for x in set.iter().filter(|x| edges.contains_key(*x)) {
+        let message = format!("the final state {x} has transitions, which are never taken");
+        self.report(Severity::Warning, key, None, message);
+      }
+    }
+    // States reachable from the initial one.
+    let mut reachable = HashSet::from([initial.clone()]);
+    let mut queue = VecDeque::from([initial]);
+    while let Some(x) = queue.pop_front() {
+      for y in edges.get(&x).into_iter().flatten() {
+        if reachable.insert(y.clone()) {
+          queue.push_back(y.clone());
+        }
+      }
+    }
+    let mut states: BTreeSet<String> = accept.union(&reject).cloned().collect();
+    states.extend(
+      rtm.transition.iter().flat_map(|x| [x.get_ref().from.name(), x.get_ref().next.name()]),
+    );
+    for x in states.iter().filter(|x| !reachable.contains(*x)) {
+      let message = format!("the state {x} is unreachable from the initial state");
+      self.report(Severity::Warning, "states", None, message);
+    }
+    for (i, tr) in rtm.transition.iter().enumerate() {
+      let next = tr.get_ref().next.name();
+      if !edges.contains_key(&next) && !is_final(&next) {
+        let location = format!("transition {i} ({})", tr.get_ref().from.name());
+        let message = format!("the next state {next} isn't final and has no transitions");
+        self.report(Severity::Warning, &location, Some(tr.span()), message);
+      }
+    }
+  }
+
+  /// Check the symbols written in each tape that are never read in it. Tapes where a transition
+  /// reads any symbol are skipped.
+  fn check_symbols(&mut self, rtm: &RawTuringMachine) {
+    let transitions = rtm.transition.iter().map(|x| x.get_ref());
+    let reads: Vec<Vec<Pattern>> = transitions
+      .clone()
+      .filter_map(|x| x.read.get_ref().read(&mut self.symbols, rtm.patterns).ok())
+      .collect();
+    let writes: Vec<Vec<WritePattern>> = transitions
+      .filter_map(|x| x.write.get_ref().write(&mut self.symbols, rtm.patterns).ok())
+      .collect();
+    for tape in 0..*rtm.ntapes.get_ref() {
+      let column = reads.iter().filter_map(|x| x.get(tape));
+      if column.clone().any(|x| *x == Pattern::Any) {
+        continue;
+      }
+      let read: HashSet<char> = column.flat_map(|x| x.symbols()).copied().collect();
+      let written: BTreeSet<char> = writes
+        .iter()
+        .filter_map(|x| match x.get(tape) {
+          | Some(WritePattern::Symbol(x)) => Some(*x),
+          | _ => None,
+        })
+        .collect();
+      for x in written.iter().filter(|x| !read.contains(*x)) {
+        let message =
+          format!("the symbol {} is written in tape {tape}, but never read", self.symbols.name(*x));
+        self.report(Severity::Warning, "symbols", None, message);
+      }
+    }
+  }
+}
+
+/// Check the configuration, returning every problem found, the errors first.
+pub fn check(rtm: &RawTuringMachine) -> Vec<Diagnostic> {
+  let mut linter = Linter::default();
+  linter.check_tapes(rtm);
+  linter.check_transitions(rtm);
+  linter.check_states(rtm);
+  linter.check_symbols(rtm);
+  linter.diagnostics.sort_by_key(|x| x.severity);
+  linter.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::lint::{Severity, check};
+  use crate::parser::parse_toml;
+
+  #[test]
+  fn test_check() {
+    let config = r#"
+      ntapes = 1
+      initial = "start"
+      accept = ["done"]
+
+      [[transition]]
+      from = "start"
+      next = "done"
+      read = "a"
+      write = "ab"
+      direction = "X"
+
+      [[transition]]
+      from = "start"
+      next = "scan"
+      read = "a"
+      write = "c"
+      direction = "R"
+
+      [[transition]]
+      from = "done"
+      next = "done"
+      read = "a"
+      write = "a"
+      direction = "S"
+
+      [[transition]]
+      from = "lost"
+      next = "done"
+      read = "b"
+      write = "b"
+      direction = "S"
+    "#;
+    let report: Vec<String> =
+      check(&parse_toml(config).unwrap()).iter().map(|x| x.to_string()).collect();
+    assert_eq!(
+      report,
+      vec![
+        "error: transition 0 (start): writes 2 symbols, but there are 1 tapes",
+        "error: transition 0 (start): unknown direction X",
+        "error: transition 1 (start): is nondeterministic with transition 0, reading [a]",
+        "warning: accept: the final state done has transitions, which are never taken",
+        "warning: states: the state lost is unreachable from the initial state",
+        "warning: transition 1 (start): the next state scan isn't final and has no transitions",
+        "warning: symbols: the symbol c is written in tape 0, but never read",
+      ]
+    );
+    let patterns = r#"
+      ntapes = 1
+      initial = 0
+      accept = [0]
+      patterns = true
+
+      [[transition]]
+      from = 0
+      next = 0
+      read = "[ab"
+      write = "="
+      direction = "R"
+    "#;
+    let report: Vec<String> =
+      check(&parse_toml(patterns).unwrap()).iter().map(|x| x.to_string()).collect();
+    assert_eq!(report[0], "error: transition 0 (0): invalid pattern [ab");
+    // Without `patterns`, the same transition reads three symbols.
+    let plain = patterns.replace("patterns = true", "");
+    let report: Vec<String> =
+      check(&parse_toml(&plain).unwrap()).iter().map(|x| x.to_string()).collect();
+    assert_eq!(report[0], "error: transition 0 (0): reads 3 symbols, but there are 1 tapes");
+    let valid = "ntapes = 1\ninitial = 0\naccept = [0]\ntransition = []";
+    assert!(check(&parse_toml(valid).unwrap()).iter().all(|x| x.severity != Severity::Error));
+  }
+}
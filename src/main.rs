@@ -5,82 +5,274 @@
 //!
 //! Main file of the crate.
 
-mod error;
-mod parser;
-mod turing_machine;
+mod cli;
+mod commands;
 
-use crate::error::Error;
-use crate::parser::Args;
-use clap::Parser;
-use std::fs::File;
-use std::io::Read;
+use crate::cli::{Args, Command, FormatArg, MachineArgs, RunArgs, SourceArgs, TraceFormatArg};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+use turing_machine_simulator::parser::RawTuringMachine;
+use turing_machine_simulator::turing_machine::{Dump, Outcome, PathStep, TraceFormat};
+use turing_machine_simulator::{
+  Error, TapeOutput, Transition, TuringMachine, jflap, morphett, parser, turingmachine_io,
+};
 
 /// Print the result of the simulator run.
-fn print_result(string: &str, result: Result<bool, Error>) {
-  if result.is_err() {
-    println!("\"{string}\" -> {}", result.err().unwrap().to_string());
-  } else if result.unwrap() == true {
-    println!("\"{string}\" -> true");
-  } else {
-    println!("\"{string}\" -> false");
+fn print_result(string: &str, outcome: Outcome) {
+  match outcome {
+    | Outcome::Loop(cycle) => println!("\"{string}\" -> {outcome} ({cycle})"),
+    | _ => println!("\"{string}\" -> {outcome}"),
   }
 }
 
-/// If using the flag -s|--shell, return to the shell an error if not accepted.
-fn return_shell(string: &str, result: Result<bool, Error>) -> Result<(), Error> {
-  if result.is_err() {
-    return Err(Error::Fail(string.to_string()));
-  } else if result.is_ok() && result.unwrap() == false {
-    return Err(Error::Fail(string.to_string()));
-  } else {
-    return Ok(());
+/// Print the content of the output tape of the simulator run.
+fn print_output(tm: &TuringMachine, string: &str, output: Option<TapeOutput>) {
+  match output {
+    | Some(x) => println!("\"{string}\" => \"{}\" (head at {})", x.render(tm.symbols()), x.head),
+    | None => println!("\"{string}\" => no output"),
+  }
+}
+
+/// Returns the human-readable representation of a transition.
+fn format_transition(tm: &TuringMachine, state: usize, read: &[char], tr: &Transition) -> String {
+  let read = tm.symbols().format(read);
+  let write = tm.symbols().format(tr.write_slice());
+  let direction: String = tr.move_slice().iter().map(|x| x.to_string()).collect();
+  let (from, next) = (tm.state_name(state), tm.state_name(tr.next()));
+  format!("{from} [{read}] -> {next} [{write}] [{direction}]")
+}
+
+/// Print the path followed by the branch that accepted the string.
+fn print_path(tm: &TuringMachine, path: &[PathStep]) {
+  for step in path {
+    println!("  {}", format_transition(tm, step.state, &step.read, &step.transition));
+  }
+}
+
+/// If using the flag -s|--shell, return to the shell a distinct exit code for each outcome.
+/// The exit code 1 is reserved for the errors.
+fn exit_code(outcome: Outcome) -> ExitCode {
+  match outcome {
+    | Outcome::Accepted => ExitCode::SUCCESS,
+    | Outcome::Rejected => ExitCode::from(2),
+    | Outcome::HaltedUndefined => ExitCode::from(3),
+    | Outcome::StepLimit => ExitCode::from(4),
+    | Outcome::Timeout => ExitCode::from(5),
+    | Outcome::SpaceLimit => ExitCode::from(6),
+    | Outcome::Loop(_) => ExitCode::from(7),
   }
 }
 
 /// Auxiliar function to get the dump file (if exists).
 fn get_dump_file(option: Option<String>) -> Result<Option<File>, Error> {
-  if option.is_some() {
-    let path = option.unwrap();
-    let result = File::create(path);
-    if result.is_err() {
-      Err(Error::ErrorOpenFile(result.err().unwrap().to_string()))
-    } else {
-      Ok(Some(result.unwrap()))
+  match option {
+    | Some(path) => match File::create(path) {
+      | Ok(file) => Ok(Some(file)),
+      | Err(x) => Err(Error::OpenFile(x.to_string())),
+    },
+    | None => Ok(None),
+  }
+}
+
+/// Auxiliar function to get the strings to test, from the arguments and the input file.
+fn get_strings(strings: &[String], input: Option<&str>) -> Result<Vec<String>, Error> {
+  let mut strings = strings.to_vec();
+  let content = match input {
+    | Some("-") => io::read_to_string(io::stdin()),
+    | Some(path) => fs::read_to_string(path),
+    | None => return Ok(strings),
+  };
+  let content = content.map_err(|x| Error::OpenFile(x.to_string()))?;
+  strings.extend(content.lines().map(|x| x.to_string()));
+  Ok(strings)
+}
+
+/// Print how many strings ended with each outcome, and how many were invalid.
+fn print_summary(outcomes: &[Result<Outcome, Error>]) {
+  let all = [
+    Outcome::Accepted,
+    Outcome::Rejected,
+    Outcome::HaltedUndefined,
+    Outcome::StepLimit,
+    Outcome::Timeout,
+    Outcome::SpaceLimit,
+    Outcome::Loop(Default::default()),
+  ];
+  let mut counts: Vec<String> = all
+    .iter()
+    .map(|x| (x, outcomes.iter().flatten().filter(|y| y.same_kind(x)).count()))
+    .filter(|x| x.1 > 0)
+    .map(|x| format!("{} {}", x.1, x.0))
+    .collect();
+  let invalid = outcomes.iter().filter(|x| x.is_err()).count();
+  if invalid > 0 {
+    counts.push(format!("{invalid} invalid"));
+  }
+  println!("Summary: {} strings, {}.", outcomes.len(), counts.join(", "));
+}
+
+/// Run a single string, printing its result.
+fn run_string(
+  tm: &TuringMachine, args: &RunArgs, string: &str, dump: Option<Dump>,
+) -> Result<Outcome, Error> {
+  let outcome = if args.path {
+    let (outcome, path) = tm.run_path(string)?;
+    if let Some(path) = path.filter(|_| outcome == Outcome::Accepted) {
+      print_path(tm, &path);
     }
+    outcome
+  } else if args.output {
+    let (outcome, output) = tm.run_transducer(string, dump)?;
+    print_output(tm, string, output);
+    outcome
   } else {
-    Ok(None)
+    tm.run(string, dump)?
+  };
+  if !args.output || args.verbose {
+    print_result(string, outcome);
   }
+  Ok(outcome)
 }
 
-fn main() -> Result<(), Error> {
-  let args: Args = Args::parse();
-  // Open the Turing machine configuration file.
-  let turing_file = File::open(args.turing_path);
-  if turing_file.is_err() {
-    return Err(Error::ErrorOpenFile(turing_file.err().unwrap().to_string()));
-  }
-  // Read the Turing machine configuration.
-  let mut turing_file_str = String::new();
-  if let Err(x) = turing_file.unwrap().read_to_string(&mut turing_file_str) {
-    return Err(Error::ErrorOpenFile(x.to_string()));
-  }
-  // Parse the toml file.
-  let rtm = parser::parse_toml(&turing_file_str);
-  if rtm.is_err() {
-    return Err(Error::ErrorParsing(rtm.err().unwrap().to_string()));
-  }
-  // Parse the Turing configuration into a turing machine.
-  let turing_machine = parser::parse(&rtm.unwrap())?;
-  // Get the string to test.
-  let test_string = args.string.clone();
+/// Print each error of an invalid configuration, with a snippet of the source pointing to it.
+fn print_errors(path: &str, source: &str, error: &Error) {
+  if let Error::Invalid(errors) = error {
+    for x in errors {
+      eprintln!("{}\n", x.render(path, source));
+    }
+  }
+}
+
+/// Returns the format of the file, if not given, by its extension: `.jff` for JFLAP, `.yaml` or
+/// `.yml` for turingmachine.io, `.tm` for Morphett, and toml for any other.
+fn file_format(path: &str, format: Option<FormatArg>) -> FormatArg {
+  if let Some(format) = format {
+    return format;
+  }
+  let extension = Path::new(path).extension().and_then(|x| x.to_str()).map(str::to_lowercase);
+  match extension.as_deref() {
+    | Some("jff") => FormatArg::Jflap,
+    | Some("yaml" | "yml") => FormatArg::Yaml,
+    | Some("tm") => FormatArg::Morphett,
+    | _ => FormatArg::Toml,
+  }
+}
+
+/// Parse the raw configuration from its source, in the format of the file. The features of the
+/// other formats that are left out are printed as warnings.
+fn parse_raw(
+  path: &str, format: Option<FormatArg>, source: &str,
+) -> Result<RawTuringMachine, Error> {
+  let import = match file_format(path, format) {
+    | FormatArg::Toml => {
+      return parser::parse_toml(source).inspect_err(|x| print_errors(path, source, x));
+    },
+    | FormatArg::Jflap => jflap::from_str(source)?,
+    | FormatArg::Yaml => turingmachine_io::from_str(source)?,
+    | FormatArg::Morphett => morphett::from_str(source)?,
+  };
+  for x in &import.warnings {
+    eprintln!("warning: {x}");
+  }
+  Ok(import.machine)
+}
+
+/// Read and parse the Turing machine configuration.
+fn read_machine(args: &SourceArgs) -> Result<TuringMachine, Error> {
+  let path = &args.turing_path;
+  let source = fs::read_to_string(path).map_err(|x| Error::OpenFile(x.to_string()))?;
+  let raw = parse_raw(path, args.format, &source)?;
+  parser::parse(&raw).inspect_err(|x| print_errors(path, &source, x))
+}
+
+/// Read and parse the Turing machine configuration, applying the limits of the arguments.
+fn load_machine(args: &MachineArgs) -> Result<TuringMachine, Error> {
+  let mut turing_machine = read_machine(&args.source)?;
+  if let Some(budget) = args.budget {
+    turing_machine.set_budget(budget);
+  }
+  let mut limits = *turing_machine.limits();
+  if let Some(steps) = args.max_steps {
+    limits.steps = steps;
+  }
+  if let Some(timeout) = args.timeout {
+    limits.timeout = Some(Duration::from_millis(timeout));
+  }
+  if let Some(cells) = args.max_cells {
+    limits.cells = Some(cells);
+  }
+  turing_machine.set_limits(&limits);
+  if args.detect_cycles {
+    turing_machine.set_cycle_detection(true);
+  }
+  Ok(turing_machine)
+}
+
+/// Run the strings on the Turing machine, printing their results.
+fn run(args: &RunArgs) -> Result<ExitCode, Error> {
+  let turing_machine = load_machine(&args.machine)?;
+  let format = match args.trace_format {
+    | TraceFormatArg::Text => TraceFormat::Text,
+    | TraceFormatArg::Jsonl => TraceFormat::JsonLines { tapes: args.trace_tapes },
+  };
+  // Get the strings to test.
+  let strings = get_strings(&args.strings, args.input.as_deref())?;
   // Get the dump file.
-  let dump_file = get_dump_file(args.dump)?;
-  // Run.
-  let result = turing_machine.run(&test_string, dump_file);
-  if args.shell > 0 {
-    return return_shell(&test_string, result);
-  } else {
-    print_result(&test_string, result);
-    Ok(())
+  let mut dump_file = get_dump_file(args.dump.clone())?;
+  // Run each string, reusing the same Turing machine.
+  let mut outcomes = Vec::new();
+  for string in &strings {
+    let dump = dump_file.as_mut().map(|x| Dump { file: x as &mut dyn Write, format });
+    // A string that isn't a valid input is reported, and the rest are still run.
+    match run_string(&turing_machine, args, string, dump) {
+      | Err(x @ (Error::InvalidInput(..) | Error::ReservedInput(..))) => {
+        println!("\"{string}\" -> invalid: {x}");
+        outcomes.push(Err(x));
+      },
+      | x => outcomes.push(Ok(x?)),
+    }
   }
+  if outcomes.len() > 1 {
+    print_summary(&outcomes);
+  }
+  // The exit code is the one of the first string not accepted, or 1 if it was invalid.
+  let outcome = outcomes.into_iter().find(|x| *x != Ok(Outcome::Accepted));
+  match outcome {
+    | _ if args.shell == 0 => Ok(ExitCode::SUCCESS),
+    | Some(Ok(outcome)) => Ok(exit_code(outcome)),
+    | Some(Err(_)) => Ok(ExitCode::FAILURE),
+    | None => Ok(ExitCode::SUCCESS),
+  }
+}
+
+fn main() -> ExitCode {
+  let args: Args = Args::parse();
+  let result = match &args.command {
+    | Some(Command::Test(x)) => commands::test::test(x),
+    | Some(Command::Debug(x)) => commands::debug::debug(x),
+    | Some(Command::Tui(x)) => commands::tui::tui(x),
+    | Some(Command::Check(x)) => commands::check::check(x),
+    | Some(Command::Import(x)) => commands::import::import(x),
+    | Some(Command::Export(x)) => commands::export::export(x),
+    | Some(Command::Graph(x)) => commands::graph::graph(x),
+    | Some(Command::Replay(x)) => commands::replay::replay(x),
+    | Some(Command::Tikz(x)) => commands::tikz::tikz(x),
+    | None => match &args.run {
+      | Some(x) => run(x),
+      // Without a subcommand, the arguments to run the strings are required.
+      | None => {
+        let message = "the arguments to run the strings are required without a subcommand";
+        Args::command().error(ErrorKind::MissingRequiredArgument, message).exit()
+      },
+    },
+  };
+  result.unwrap_or_else(|x| {
+    eprintln!("Error: {x}");
+    ExitCode::FAILURE
+  })
 }
@@ -0,0 +1,204 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Import of Turing machines in the text format of morphett.info.
+//!
+//! Each line is a rule `<state> <symbol> <new symbol> <direction> <new state>`, and anything after
+//! a `;` is a comment. The blank is `_`, the directions are `l`, `r` and `*` to stay, and `*`
+//! means any symbol read, the same symbol written, or staying in the same state.
+//!
+//! A `*` in the state is expanded into a rule for every state of the file, except the states that
+//! already have a rule for that symbol, or for any symbol, so the rules of a state always come
+//! first, as in the original simulator. The machine starts in the state `0`, or in the first
+//! state of the file if there isn't any. The states whose name starts with `halt` are final:
+//! `halt-reject` ones reject, and any other accepts.
+
+use crate::error::{Error, line_col};
+use crate::parser::{Import, RawState, RawSymbols, RawTransition, RawTuringMachine};
+use crate::pattern;
+use std::ops::Range;
+use toml::Spanned;
+
+/// Name of the blank in Morphett.
+pub const MORPHETT_BLANK: &str = "_";
+/// Symbol and state of Morphett that match anything, or keep what there is.
+const WILDCARD: &str = "*";
+
+/// Single rule of the file, with the range of each field.
+struct Rule<'a> {
+  /// Range of the whole rule.
+  span: Range<usize>,
+  /// Text and range of each field.
+  fields: [(&'a str, Range<usize>); 5],
+}
+
+/// Returns an import error, with the location in the source where it was found.
+fn error(source: &str, offset: usize, message: String) -> Error {
+  let (line, column) = line_col(source, offset);
+  Error::Import(format!("{message} (line {line}, column {column})"))
+}
+
+/// Returns the rules of the file, leaving out the comments, the empty lines and the breakpoints.
+fn rules(source: &str) -> Result<Vec<Rule<'_>>, Error> {
+  let mut rules = Vec::new();
+  let mut start = 0;
+  for line in source.split_inclusive('\n') {
+    let code = line.split(';').next().unwrap_or_default();
+    let mut fields: Vec<(&str, Range<usize>)> = code
+      .split_whitespace()
+      .map(|x| {
+        let offset = start + (x.as_ptr() as usize - line.as_ptr() as usize);
+        (x, offset..offset + x.len())
+      })
+      .collect();
+    if fields.last().is_some_and(|x| x.0 == "!") {
+      fields.pop();
+    }
+    if let (Some(first), Some(last)) = (fields.first(), fields.last()) {
+      let span = first.1.start..last.1.end;
+      let Ok(fields) = fields.try_into() else {
+        let message = "the rule doesn't have 5 fields".to_string();
+        return Err(error(source, span.start, message));
+      };
+      rules.push(Rule { span, fields });
+    }
+    start += line.len();
+  }
+  Ok(rules)
+}
+
+/// Returns the symbol as written in a configuration string.
+fn symbol(source: &str, (text, span): &(&str, Range<usize>), write: bool) -> Result<String, Error> {
+  let mut chars = text.chars();
+  match (chars.next(), chars.next()) {
+    | _ if *text == WILDCARD => Ok(if write { "=" } else { "*" }.to_string()),
+    | _ if *text == MORPHETT_BLANK => Ok(MORPHETT_BLANK.to_string()),
+    | (Some(x), None) => Ok(pattern::escape(x)),
+    | _ => {
+      Err(error(source, span.start, format!("the symbol \"{text}\" has more than one character")))
+    },
+  }
+}
+
+/// Returns the transition of a rule, from the given state.
+fn transition(source: &str, rule: &Rule, from: &str) -> Result<Spanned<RawTransition>, Error> {
+  let [_, read, write, direction, next] = &rule.fields;
+  let moves = match direction.0 {
+    | "l" | "L" => "L",
+    | "r" | "R" => "R",
+    | "*" => "S",
+    | x => return Err(error(source, direction.1.start, format!("unknown direction \"{x}\""))),
+  };
+  let next = if next.0 == WILDCARD { from } else { next.0 };
+  let transition = RawTransition {
+    from: RawState::Name(from.to_string()),
+    next: RawState::Name(next.to_string()),
+    read: Spanned::new(read.1.clone(), RawSymbols::Chars(symbol(source, read, false)?)),
+    write: Spanned::new(write.1.clone(), RawSymbols::Chars(symbol(source, write, true)?)),
+    direction: Spanned::new(direction.1.clone(), moves.to_string()),
+  };
+  Ok(Spanned::new(rule.span.clone(), transition))
+}
+
+/// Import the Turing machine of the content of a Morphett file.
+pub fn from_str(source: &str) -> Result<Import, Error> {
+  let rules = rules(source)?;
+  // States in order of appearance.
+  let mut states: Vec<&str> = Vec::new();
+  for x in rules.iter().flat_map(|x| [x.fields[0].0, x.fields[4].0]) {
+    if x != WILDCARD && !states.contains(&x) {
+      states.push(x);
+    }
+  }
+  let initial = match states.iter().find(|x| **x == "0").or(states.first()) {
+    | Some(x) => x.to_string(),
+    | None => return Err(Error::Import("the file has no rules".to_string())),
+  };
+  let halts = |x: &&str| x.starts_with("halt");
+  let names = |x: Vec<&str>| x.into_iter().map(|x| RawState::Name(x.to_string())).collect();
+  let (reject, accept): (Vec<&str>, Vec<&str>) =
+    states.iter().copied().filter(halts).partition(|x| x.starts_with("halt-reject"));
+  // The rules of a state come before the rules of any state.
+  let mut transition = Vec::new();
+  for rule in rules.iter().filter(|x| x.fields[0].0 != WILDCARD) {
+    transition.push(self::transition(source, rule, rule.fields[0].0)?);
+  }
+  for rule in rules.iter().filter(|x| x.fields[0].0 == WILDCARD) {
+    let read = rule.fields[1].0;
+    for state in states.iter().filter(|x| !halts(x)) {
+      let shadowed = rules
+        .iter()
+        .any(|x| x.fields[0].0 == *state && (x.fields[1].0 == read || x.fields[1].0 == WILDCARD));
+      if !shadowed {
+        transition.push(self::transition(source, rule, state)?);
+      }
+    }
+  }
+  let machine = RawTuringMachine {
+    ntapes: Spanned::new(0..0, 1),
+    initial: RawState::Name(initial),
+    accept: names(accept),
+    reject: names(reject),
+    nondeterministic: false,
+    budget: None,
+    output: None,
+    blank: Some(Spanned::new(0..0, MORPHETT_BLANK.to_string())),
+    blank_glyph: None,
+    input_alphabet: None,
+    tape_alphabet: None,
+    max_steps: None,
+    timeout: None,
+    max_cells: None,
+    detect_cycles: false,
+    patterns: true,
+    transition,
+  };
+  Ok(Import { machine, warnings: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::morphett::from_str;
+  use crate::parser;
+  use crate::turing_machine::Outcome;
+
+  /// Machine that accepts the palindromes of 0's and 1's.
+  const MACHINE: &str = "
+; Palindromes
+0 0 _ r 1o
+0 1 _ r 1i
+0 _ _ * halt-accept
+1o * * r 1o
+1o _ _ l 2o
+1i * * r 1i
+1i _ _ l 2i
+2o 0 _ l 3
+2i 1 _ l 3
+2o _ _ * halt-accept
+2i _ _ * halt-accept
+* 0 0 * halt-reject  ; no match
+* 1 1 * halt-reject
+3 _ _ r 0 !
+3 * * l 3
+";
+
+  #[test]
+  fn test_import() {
+    let import = from_str(MACHINE).unwrap();
+    assert!(import.warnings.is_empty());
+    let tm = parser::parse(&import.machine).unwrap();
+    assert_eq!(tm.state_name(tm.initial()), "0");
+    for (input, outcome) in [("0110", Outcome::Accepted), ("101", Outcome::Accepted)] {
+      assert_eq!(tm.run(input, None), Ok(outcome));
+    }
+    assert_eq!(tm.run("10", None), Ok(Outcome::Rejected));
+    assert_eq!(tm.run("", None), Ok(Outcome::Accepted));
+    let Err(Error::Import(x)) = from_str("0 a b r") else { panic!("expected an import error") };
+    assert_eq!(x, "the rule doesn't have 5 fields (line 1, column 1)");
+    let Err(Error::Import(x)) = from_str("\n0 a b x 1") else { panic!("expected an import error") };
+    assert_eq!(x, "unknown direction \"x\" (line 2, column 7)");
+  }
+}
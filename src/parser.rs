@@ -5,70 +5,370 @@
 //!
 //! Main module for the parser.
 
-/// Represents a blank in a tape's cell.
-pub const BLANK: char = '\0';
-/// How the blanks will be printed.
-pub const BLANK_REP: char = 'β';
-
-use crate::error::Error;
-use crate::turing_machine::TuringMachine;
-use crate::turing_machine::transition::{Direction, Transition};
-use clap::Parser;
-use serde::Deserialize;
-use std::collections::HashSet;
+use crate::error::{Error, Located};
+use crate::pattern::{self, Pattern, WritePattern};
+use crate::turing_machine::transition::{Direction, PatternTransition, Transition};
+use crate::turing_machine::{DEFAULT_BUDGET, DEFAULT_MAX_STEPS, Limits, Symbols, TuringMachine};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::ops::{Not, Range};
+use std::path::Path;
+use std::time::Duration;
+use toml::Spanned;
 
 /// Struct representing an raw, not checked turing machine.
-#[derive(Debug, Default, Clone, Deserialize)]
+/// The fields that can be wrong keep their location in the source.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RawTuringMachine {
-  ntapes: usize,
-  accept: Vec<usize>,
-  initial: usize,
-  transition: Vec<RawTransition>,
+  pub(crate) ntapes: Spanned<usize>,
+  pub(crate) initial: RawState,
+  pub(crate) accept: Vec<RawState>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) reject: Vec<RawState>,
+  #[serde(default, skip_serializing_if = "Not::not")]
+  pub(crate) nondeterministic: bool,
+  pub(crate) budget: Option<usize>,
+  pub(crate) output: Option<Spanned<usize>>,
+  pub(crate) blank: Option<Spanned<String>>,
+  pub(crate) blank_glyph: Option<String>,
+  pub(crate) input_alphabet: Option<Spanned<RawSymbols>>,
+  pub(crate) tape_alphabet: Option<Spanned<RawSymbols>>,
+  pub(crate) max_steps: Option<usize>,
+  pub(crate) timeout: Option<u64>,
+  pub(crate) max_cells: Option<usize>,
+  #[serde(default, skip_serializing_if = "Not::not")]
+  pub(crate) detect_cycles: bool,
+  #[serde(default, skip_serializing_if = "Not::not")]
+  pub(crate) patterns: bool,
+  pub(crate) transition: Vec<Spanned<RawTransition>>,
+}
+
+/// Machine imported from the file format of another simulator.
+#[derive(Debug, Clone)]
+pub struct Import {
+  /// Configuration of the machine.
+  pub machine: RawTuringMachine,
+  /// Features of the file that the configuration can't represent, and were left out.
+  pub warnings: Vec<String>,
 }
 
 /// Struct representing a raw, not checked transition for the turing machine.
-#[derive(Debug, Default, Clone, Deserialize)]
-struct RawTransition {
-  from: usize,
-  next: usize,
-  read: String,
-  write: String,
-  direction: String,
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct RawTransition {
+  pub(crate) from: RawState,
+  pub(crate) next: RawState,
+  pub(crate) read: Spanned<RawSymbols>,
+  pub(crate) write: Spanned<RawSymbols>,
+  pub(crate) direction: Spanned<String>,
+}
+
+/// Symbols in the configuration, either as a string with a character per symbol, or as a list
+/// with the name of each symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(untagged, expecting = "expected a string or a list of symbols")]
+pub(crate) enum RawSymbols {
+  Chars(String),
+  List(Vec<String>),
+}
+
+impl RawSymbols {
+  /// Returns the symbols, interning the names longer than a single character.
+  pub(crate) fn symbols(&self, symbols: &mut Symbols) -> Result<Vec<char>, Error> {
+    match self {
+      | RawSymbols::Chars(x) => x.chars().map(|x| symbols.symbol(x)).collect(),
+      | RawSymbols::List(x) => x.iter().map(|x| symbols.intern(x)).collect(),
+    }
+  }
+
+  /// Returns the pattern read in each tape. Without `patterns`, every tape reads a plain symbol.
+  pub(crate) fn read(&self, symbols: &mut Symbols, patterns: bool) -> Result<Vec<Pattern>, Error> {
+    match self {
+      | RawSymbols::Chars(x) if patterns => pattern::parse_read(x, symbols),
+      | RawSymbols::List(x) if patterns => {
+        x.iter().map(|x| pattern::parse_read_name(x, symbols)).collect()
+      },
+      | x => Ok(x.symbols(symbols)?.into_iter().map(Pattern::Symbol).collect()),
+    }
+  }
+
+  /// Returns the pattern written in each tape. Without `patterns`, every tape writes a plain
+  /// symbol.
+  pub(crate) fn write(
+    &self, symbols: &mut Symbols, patterns: bool,
+  ) -> Result<Vec<WritePattern>, Error> {
+    match self {
+      | RawSymbols::Chars(x) if patterns => pattern::parse_write(x, symbols),
+      | RawSymbols::List(x) if patterns => {
+        x.iter().map(|x| pattern::parse_write_name(x, symbols)).collect()
+      },
+      | x => Ok(x.symbols(symbols)?.into_iter().map(WritePattern::Symbol).collect()),
+    }
+  }
+}
+
+/// A state in the configuration, identified either by an integer or by a name.
+/// An integer is the index of the state, while a name is interned to the next index free.
+/// Writing the same state as `3` and as `"3"` is an error.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum RawState {
+  Index(usize),
+  Name(String),
+}
+
+impl Default for RawState {
+  fn default() -> Self {
+    RawState::Index(0)
+  }
+}
+
+impl RawState {
+  /// Returns the human-readable name of the state.
+  pub(crate) fn name(&self) -> String {
+    match self {
+      | RawState::Index(x) => x.to_string(),
+      | RawState::Name(x) => x.clone(),
+    }
+  }
+}
+
+/// Table interning the name of each state to an index.
+#[derive(Debug, Default)]
+struct StateTable {
+  indices: HashMap<String, usize>,
+  names: Vec<String>,
+}
+
+impl StateTable {
+  /// Creates the table of the configuration, reserving to the states written as an integer their
+  /// own index, so the named states are interned after the greatest of them.
+  fn new(rtm: &RawTuringMachine) -> Self {
+    let header = std::iter::once(&rtm.initial).chain(&rtm.accept).chain(&rtm.reject);
+    let transitions = rtm.transition.iter().flat_map(|x| [&x.get_ref().from, &x.get_ref().next]);
+    let max = header.chain(transitions).filter_map(|x| match x {
+      | RawState::Index(x) => Some(*x),
+      | RawState::Name(_) => None,
+    });
+    let names: Vec<String> = (0..max.max().map_or(0, |x| x + 1)).map(|x| x.to_string()).collect();
+    let indices = names.iter().enumerate().map(|(i, x)| (x.clone(), i)).collect();
+    StateTable { indices, names }
+  }
+
+  /// Returns the index of the state, interning it if it is the first time found.
+  fn intern(&mut self, state: &RawState) -> usize {
+    let name = state.name();
+    if let Some(x) = self.indices.get(&name) {
+      return *x;
+    }
+    self.names.push(name.clone());
+    self.indices.insert(name, self.names.len() - 1);
+    self.names.len() - 1
+  }
 }
 
 /// Tries to parse a toml to a RawTuringMachine.
-pub fn parse_toml(raw: &str) -> Result<RawTuringMachine, Box<dyn std::error::Error>> {
-  let rtm: RawTuringMachine = toml::from_str(raw)?;
-  Ok(rtm)
+pub fn parse_toml(raw: &str) -> Result<RawTuringMachine, Error> {
+  toml::from_str(raw).map_err(|x| {
+    let error = Error::Parsing(x.message().trim_end().to_string());
+    Error::Invalid(vec![Located { error, transition: None, span: x.span() }])
+  })
+}
+
+/// Returns the toml configuration of a RawTuringMachine.
+pub fn to_toml(rtm: &RawTuringMachine) -> Result<String, Error> {
+  toml::to_string(rtm).map_err(|x| Error::WriteFile(x.to_string()))
+}
+
+/// Parse a TuringMachine directly from the content of a toml configuration.
+pub fn from_str(raw: &str) -> Result<TuringMachine, Error> {
+  parse(&parse_toml(raw)?)
+}
+
+/// Read and parse a TuringMachine from the toml configuration located in `path`.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<TuringMachine, Error> {
+  let raw = fs::read_to_string(path).map_err(|x| Error::OpenFile(x.to_string()))?;
+  from_str(&raw)
 }
 
 /// Parse a TuringMachine from a RawTuringMachine
+/// The states written as an integer keep it as their index, and the named ones are interned after
+/// them in order of appearance.
+/// Every wrong transition is reported at once, as an Error::Invalid with the location of each one.
 pub fn parse(rtm: &RawTuringMachine) -> Result<TuringMachine, Error> {
-  let accept_set = HashSet::from_iter(rtm.accept.iter().cloned());
-  let mut tm = TuringMachine::new(rtm.initial, rtm.ntapes, &accept_set)?;
-  // For each transition.
-  for tr in &rtm.transition {
-    // Characters readed.
-    let read: Vec<char> = Vec::from_iter(tr.read.chars())
-      .iter()
-      .map(|x| if *x == BLANK_REP { BLANK } else { *x })
-      .collect();
-    // Characters writen.
-    let write: Vec<char> = Vec::from_iter(tr.write.chars())
+  let mut states = StateTable::new(rtm);
+  let initial = states.intern(&rtm.initial);
+  let accept_set = HashSet::from_iter(rtm.accept.iter().map(|x| states.intern(x)));
+  let reject_set = HashSet::from_iter(rtm.reject.iter().map(|x| states.intern(x)));
+  let transitions: Vec<(usize, usize)> = rtm
+    .transition
+    .iter()
+    .map(|tr| tr.get_ref())
+    .map(|tr| (states.intern(&tr.from), states.intern(&tr.next)))
+    .collect();
+  let ntapes = *rtm.ntapes.get_ref();
+  let tm = if rtm.nondeterministic {
+    let budget = rtm.budget.unwrap_or(DEFAULT_BUDGET);
+    TuringMachine::new_nondeterministic(initial, ntapes, &accept_set, budget)
+  } else {
+    TuringMachine::new(initial, ntapes, &accept_set)
+  };
+  let mut tm = tm.map_err(|error| {
+    Error::Invalid(vec![Located { error, transition: None, span: Some(rtm.ntapes.span()) }])
+  })?;
+  let mut errors = mixed_states(rtm);
+  tm.set_state_names(&states.names);
+  tm.set_rejection(&reject_set);
+  if let Some(output) = &rtm.output
+    && let Err(error) = tm.set_output_tape(*output.get_ref())
+  {
+    errors.push(Located { error, transition: None, span: Some(output.span()) });
+  }
+  tm.set_limits(&Limits {
+    steps: rtm.max_steps.unwrap_or(DEFAULT_MAX_STEPS),
+    timeout: rtm.timeout.map(Duration::from_millis),
+    cells: rtm.max_cells,
+  });
+  tm.set_cycle_detection(rtm.detect_cycles);
+  let mut symbols = Symbols::default();
+  if let Some(blank) = &rtm.blank
+    && let Err(error) = symbols.set_blank(blank.get_ref(), rtm.blank_glyph.as_deref())
+  {
+    errors.push(Located { error, transition: None, span: Some(blank.span()) });
+  }
+  if let Some(alphabet) = &rtm.tape_alphabet {
+    match alphabet.get_ref().symbols(&mut symbols) {
+      | Ok(x) => tm.set_tape_alphabet(&x.into_iter().collect()),
+      | Err(error) => errors.push(Located { error, transition: None, span: Some(alphabet.span()) }),
+    }
+  }
+  if let Some(alphabet) = &rtm.input_alphabet {
+    let span = Some(alphabet.span());
+    match alphabet.get_ref().symbols(&mut symbols) {
+      | Ok(input) => {
+        if let Some(tape) = tm.tape_alphabet()
+          && let Some(x) = input.iter().find(|x| !tape.contains(x))
+        {
+          let error = Error::UnknownSymbol(symbols.name(*x));
+          errors.push(Located { error, transition: None, span });
+        }
+        tm.set_input_alphabet(&input.into_iter().collect());
+      },
+      | Err(error) => errors.push(Located { error, transition: None, span }),
+    }
+  }
+  // Parse the patterns of each transition.
+  let mut rules = Vec::new();
+  for (i, (tr, states)) in rtm.transition.iter().zip(transitions).enumerate() {
+    match Rule::parse(i, tr.get_ref(), states, ntapes, rtm.patterns, &mut symbols) {
+      | Ok(rule) => rules.push(rule),
+      | Err((error, span)) => errors.push(Located { error, transition: Some(i), span: Some(span) }),
+    }
+  }
+  tm.set_symbols(symbols);
+  // The ones reading a single symbol in each tape are inserted in the transition function, and
+  // the rest are matched while running.
+  for rule in rules {
+    let result = match rule.exact() {
+      | Some((read, transition)) => tm.insert_transition(rule.from, &read, &transition),
+      | None => tm.insert_pattern(rule.from, &rule.transition),
+    };
+    if let Err(error) = result {
+      let span = match &error {
+        | Error::UnknownSymbol(x) if tm.symbols().get(x).is_some_and(|x| !rule.reads(x)) => {
+          rule.raw.write.span()
+        },
+        | _ => rule.raw.read.span(),
+      };
+      errors.push(Located { error, transition: Some(rule.index), span: Some(span) });
+    }
+  }
+  errors.sort_by_key(|x| x.transition);
+  if errors.is_empty() { Ok(tm) } else { Err(Error::Invalid(errors)) }
+}
+
+/// Returns an error for each state written both as an integer and as a string, located where it
+/// is first written the other way, as both would be taken as the same state.
+fn mixed_states(rtm: &RawTuringMachine) -> Vec<Located> {
+  let header = std::iter::once(&rtm.initial).chain(&rtm.accept).chain(&rtm.reject);
+  let header = header.map(|x| (x, None, None));
+  let transitions = rtm.transition.iter().enumerate().flat_map(|(i, tr)| {
+    let raw = tr.get_ref();
+    [&raw.from, &raw.next].map(|x| (x, Some(i), Some(tr.span())))
+  });
+  let mut kinds: HashMap<String, bool> = HashMap::new();
+  let mut errors: Vec<Located> = Vec::new();
+  for (state, transition, span) in header.chain(transitions) {
+    let (name, index) = (state.name(), matches!(state, RawState::Index(_)));
+    if *kinds.entry(name.clone()).or_insert(index) != index
+      && !errors.iter().any(|x| x.error == Error::MixedState(name.clone()))
+    {
+      errors.push(Located { error: Error::MixedState(name), transition, span });
+    }
+  }
+  errors
+}
+
+/// A transition of the configuration, with its patterns parsed.
+struct Rule<'a> {
+  /// Index of the transition in the configuration.
+  index: usize,
+  /// Transition as found in the configuration.
+  raw: &'a RawTransition,
+  /// State before the transition.
+  from: usize,
+  /// Patterns of the transition.
+  transition: PatternTransition,
+}
+
+impl<'a> Rule<'a> {
+  /// Parse the patterns and the directions of a transition, checking there is one for each tape.
+  /// On error, returns it with the location of the field where it was found.
+  fn parse(
+    index: usize, raw: &'a RawTransition, (from, next): (usize, usize), ntapes: usize,
+    patterns: bool, symbols: &mut Symbols,
+  ) -> Result<Self, (Error, Range<usize>)> {
+    let read = raw.read.get_ref().read(symbols, patterns).map_err(|x| (x, raw.read.span()))?;
+    let write = raw.write.get_ref().write(symbols, patterns).map_err(|x| (x, raw.write.span()))?;
+    let direction =
+      map_direction_vec(raw.direction.get_ref()).map_err(|x| (x, raw.direction.span()))?;
+    let sizes = [
+      (read.len(), raw.read.span()),
+      (write.len(), raw.write.span()),
+      (direction.len(), raw.direction.span()),
+    ];
+    if let Some((len, span)) = sizes.into_iter().find(|x| x.0 != ntapes) {
+      return Err((Error::UnmatchingSizes(ntapes, len), span));
+    }
+    let transition = PatternTransition::new(&read, &write, &direction, next).unwrap();
+    Ok(Rule { index, raw, from, transition })
+  }
+
+  /// Returns the symbols read and the transition taken, if it reads a single symbol in each tape.
+  fn exact(&self) -> Option<(Vec<char>, Transition)> {
+    let read: Option<Vec<char>> = self
+      .transition
+      .read_slice()
       .iter()
-      .map(|x| if *x == BLANK_REP { BLANK } else { *x })
+      .map(|x| match x {
+        | Pattern::Symbol(x) => Some(*x),
+        | _ => None,
+      })
       .collect();
-    // Direction of each tape.
-    let direc = map_direction_vec(&tr.direction)?;
-    // Create and insert the new transition.
-    let transition = Transition::new(&write, &direc, tr.next)?;
-    tm.insert_transition(tr.from, &read, &transition)?;
+    read.map(|x| {
+      let transition = self.transition.resolve(&x);
+      (x, transition)
+    })
+  }
+
+  /// Returns true if the symbol is named in the patterns read.
+  fn reads(&self, symbol: char) -> bool {
+    self.transition.read_slice().iter().any(|x| x.symbols().contains(&symbol))
   }
-  Ok(tm) // Return the TuringMachine.
 }
 
 /// From a char, convert into a Direction.
-fn convert_direction(d: char) -> Result<Direction, Error> {
+pub(crate) fn convert_direction(d: char) -> Result<Direction, Error> {
   match d {
     | 'L' => Ok(Direction::Left),
     | 'R' => Ok(Direction::Right),
@@ -78,7 +378,7 @@ fn convert_direction(d: char) -> Result<Direction, Error> {
 }
 
 /// From a string, convert into a Vector of Direction.
-fn map_direction_vec(dir: &String) -> Result<Vec<Direction>, Error> {
+fn map_direction_vec(dir: &str) -> Result<Vec<Direction>, Error> {
   let mut vec = Vec::new();
   for d in dir.chars() {
     let direction = convert_direction(d)?;
@@ -87,18 +387,288 @@ fn map_direction_vec(dir: &String) -> Result<Vec<Direction>, Error> {
   Ok(vec)
 }
 
-#[derive(Parser, Debug, Default)]
-#[command(version, about)]
-pub struct Args {
-  /// String to be tested on the Turing machine.
-  pub string: String,
-  /// Path where the Turing machine configuration is located.
-  #[arg(short, long)]
-  pub turing_path: String,
-  /// File where the state of the tapes will be dumped.
-  #[arg(short, long)]
-  pub dump: Option<String>,
-  /// Treat failures as errors.
-  #[arg(short, long, action = clap::ArgAction::Count)]
-  pub shell: u8,
+#[cfg(test)]
+mod tests {
+  use crate::error::{Error, line_col};
+  use crate::parser::{from_file, from_str};
+  use crate::turing_machine::Outcome;
+
+  #[test]
+  fn test_named_states() {
+    let config = r#"
+      ntapes = 1
+      initial = "start"
+      accept = ["done"]
+
+      [[transition]]
+      from = "start"
+      next = 3
+      read = "a"
+      write = "a"
+      direction = "R"
+
+      [[transition]]
+      from = 3
+      next = "done"
+      read = "β"
+      write = "β"
+      direction = "S"
+    "#;
+    let tm = from_str(config).unwrap();
+    // The integer states keep their index, and the named ones come after them.
+    assert_eq!(tm.initial(), 4);
+    assert_eq!(tm.state_name(4), "start");
+    assert_eq!(tm.state_name(5), "done");
+    assert_eq!(tm.state_index("3"), Some(3));
+    assert_eq!(tm.run("a", None), Ok(Outcome::Accepted));
+    assert_eq!(tm.run("aa", None), Ok(Outcome::HaltedUndefined));
+    let mixed = config.replacen("next = 3", "next = \"3\"", 1);
+    let Err(Error::Invalid(errors)) = from_str(&mixed) else { panic!("expected an error") };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error, Error::MixedState("3".to_string()));
+    assert_eq!(errors[0].transition, Some(1));
+  }
+
+  #[test]
+  fn test_located_errors() {
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [1]
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "ab"
+      write = "a"
+      direction = "R"
+
+      [[transition]]
+      from = 1
+      next = 1
+      read = "a"
+      write = "a"
+      direction = "X"
+    "#;
+    let Err(Error::Invalid(errors)) = from_str(config) else { panic!("expected invalid") };
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].error, Error::UnmatchingSizes(1, 2));
+    assert_eq!(
+      (errors[0].transition, line_col(config, errors[0].span.clone().unwrap().start)),
+      (Some(0), (9, 14))
+    );
+    assert_eq!(errors[1].error, Error::UnkownDirection("X".to_string()));
+    assert_eq!(errors[1].to_string(), "transition 1: Found an unkown direction while parsing: X.");
+    let snippet = errors[1].render("x.toml", config);
+    assert!(snippet.ends_with("18 |       direction = \"X\"\n   |                   ^^^"));
+    let Err(Error::Invalid(errors)) = from_str("ntapes = 1\ninitial = 0\n") else { panic!() };
+    assert_eq!(errors[0].error, Error::Parsing("missing field `accept`".to_string()));
+  }
+
+  #[test]
+  fn test_unmatching_sizes() {
+    let Err(Error::Invalid(errors)) = from_file("example/unmatching_sizes.toml") else {
+      panic!("expected invalid")
+    };
+    let errors: Vec<(Option<usize>, Error)> =
+      errors.into_iter().map(|x| (x.transition, x.error)).collect();
+    let unmatching = Error::UnmatchingSizes(1, 2);
+    assert_eq!(errors, vec![(Some(0), unmatching.clone()), (Some(1), unmatching)]);
+  }
+
+  #[test]
+  fn test_alphabets() {
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [1]
+      input_alphabet = "ab"
+      tape_alphabet = "aX"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "a"
+      write = "X"
+      direction = "R"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "X"
+      write = "Y"
+      direction = "R"
+    "#;
+    let Err(Error::Invalid(errors)) = from_str(config) else { panic!("expected invalid") };
+    let errors: Vec<(Option<usize>, Error)> =
+      errors.into_iter().map(|x| (x.transition, x.error)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (None, Error::UnknownSymbol("b".to_string())),
+        (Some(1), Error::UnknownSymbol("Y".to_string()))
+      ]
+    );
+    let tm = from_str(&config.replace("\"aX\"", "\"abXY\"")).unwrap();
+    assert_eq!(tm.run("ab", None), Ok(Outcome::Accepted));
+    assert_eq!(tm.run("ac", None), Err(Error::InvalidInput("ac".to_string(), "c".to_string())));
+    let reserved = "a\u{F0000}";
+    assert_eq!(
+      tm.run(reserved, None),
+      Err(Error::ReservedInput(reserved.to_string(), '\u{F0000}'))
+    );
+  }
+
+  #[test]
+  fn test_patterns() {
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [1]
+      tape_alphabet = "abc"
+      patterns = true
+
+      [[transition]]
+      from = 0
+      next = 0
+      read = "*"
+      write = "="
+      direction = "R"
+
+      [[transition]]
+      from = 0
+      next = 0
+      read = "[ab]"
+      write = "X"
+      direction = "R"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "β"
+      write = "β"
+      direction = "S"
+    "#;
+    let tm = from_str(&config.replace("\"abc\"", "\"abcX\"")).unwrap();
+    let write = |read: char| tm.transitions(0, &[read])[0].write_slice()[0];
+    assert_eq!((write('a'), write('c'), write('X')), ('X', 'c', 'X'));
+    assert_eq!(tm.transitions(0, &['\0'])[0].next(), 1);
+    assert_eq!(tm.run("abc", None), Ok(Outcome::Accepted));
+    // Without 'X' in the tape alphabet, only the class transition fails.
+    let Err(Error::Invalid(errors)) = from_str(config) else { panic!("expected invalid") };
+    let errors: Vec<(Option<usize>, Error)> =
+      errors.into_iter().map(|x| (x.transition, x.error)).collect();
+    assert_eq!(errors, vec![(Some(1), Error::UnknownSymbol("X".to_string()))]);
+    // Transitions as specific as each other are nondeterministic.
+    let tied = config.replace("\"abc\"", "\"abcX\"").replace("\"*\"", "\"[bc]\"");
+    let Err(Error::Invalid(errors)) = from_str(&tied) else { panic!("expected invalid") };
+    let errors: Vec<(Option<usize>, Error)> =
+      errors.into_iter().map(|x| (x.transition, x.error)).collect();
+    assert_eq!(errors, vec![(Some(1), Error::Indeterminancy("0".to_string(), "b".to_string()))]);
+    // A nondeterministic machine keeps every transition, however specific.
+    let nondeterministic = config.replace("\"abc\"", "\"abcX\"\nnondeterministic = true");
+    let tm = from_str(&nondeterministic).unwrap();
+    assert_eq!((tm.transitions(0, &['a']).len(), tm.transitions(0, &['c']).len()), (2, 1));
+    // Without a tape alphabet, `*` matches any symbol.
+    let tm = from_str(&config.replace("tape_alphabet = \"abc\"", "")).unwrap();
+    assert_eq!(tm.run("xyz", None), Ok(Outcome::Accepted));
+    // Without `patterns`, they are plain symbols.
+    let Err(Error::Invalid(errors)) = from_str(&config.replace("patterns = true", "")) else {
+      panic!("expected invalid")
+    };
+    let errors: Vec<(Option<usize>, Error)> =
+      errors.into_iter().map(|x| (x.transition, x.error)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (Some(0), Error::UnknownSymbol("*".to_string())),
+        (Some(1), Error::UnmatchingSizes(1, 4))
+      ]
+    );
+  }
+
+  #[test]
+  fn test_symbol_lists() {
+    let config = r##"
+      ntapes = 2
+      initial = 0
+      accept = [1]
+      input_alphabet = ["a", "X1"]
+      tape_alphabet = ["a", "X1", "#0"]
+      patterns = true
+
+      [[transition]]
+      from = 0
+      next = 0
+      read = ["a", "β"]
+      write = ["X1", "#0"]
+      direction = "RR"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "ββ"
+      write = ["#0", "="]
+      direction = "SS"
+    "##;
+    let tm = from_str(config).unwrap();
+    let x1 = tm.symbols().get("X1").unwrap();
+    assert_eq!(tm.transitions(0, &['a', '\0'])[0].write_slice()[0], x1);
+    let (outcome, output) = tm.run_transducer("aa", None).unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    assert_eq!(output.unwrap().render(tm.symbols()), "X1X1#0");
+    // The input strings are split by the names of the symbols.
+    assert_eq!(tm.check_input("aX1a"), Ok(()));
+    assert_eq!(tm.check_input("aX"), Err(Error::InvalidInput("aX".to_string(), "X".to_string())));
+    let Err(Error::Invalid(errors)) = from_str(&config.replace("\"#0\", \"=\"", "\"Y2\", \"=\""))
+    else {
+      panic!("expected invalid")
+    };
+    assert_eq!(errors[0].error, Error::UnknownSymbol("Y2".to_string()));
+  }
+
+  #[test]
+  fn test_blank() {
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [1]
+      blank = "_"
+      blank_glyph = "□"
+      patterns = true
+
+      [[transition]]
+      from = 0
+      next = 0
+      read = "[ab]"
+      write = "b"
+      direction = "R"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "_"
+      write = "_"
+      direction = "S"
+    "#;
+    let tm = from_str(config).unwrap();
+    assert_eq!(tm.transitions(0, &['\0'])[0].next(), 1);
+    let (outcome, output) = tm.run_transducer("a_a", None).unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    let output = output.unwrap();
+    assert_eq!((output.render(tm.symbols()), output.head), ("b□a".to_string(), 1));
+    let Err(Error::Invalid(errors)) = from_str(&config.replace("\"_\"\n", "\"\"\n")) else {
+      panic!("expected invalid")
+    };
+    assert_eq!(errors[0].error, Error::InvalidSymbol(String::new()));
+    // A blank longer than a single character is written in the input strings by its name.
+    let long =
+      config.replace("blank = \"_\"", "blank = \"B0\"").replace("read = \"_\"", "read = [\"B0\"]");
+    let tm = from_str(&long.replace("write = \"_\"", "write = [\"B0\"]")).unwrap();
+    let (outcome, output) = tm.run_transducer("aB0a", None).unwrap();
+    assert_eq!(
+      (outcome, output.unwrap().render(tm.symbols())),
+      (Outcome::Accepted, "b□a".to_string())
+    );
+  }
 }
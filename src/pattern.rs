@@ -0,0 +1,248 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Patterns in the symbols read and written by the transitions of a configuration.
+//!
+//! They are only recognized in the configurations with `patterns = true`, as they take over
+//! characters that the older configurations could use as plain symbols. Each tape of `read` can
+//! then be a symbol, `*` for any symbol, or a class like `[abc]` for any of the symbols inside the
+//! brackets. Each tape of `write` can be a symbol, or `=` to write back the symbol read in that
+//! tape. A backslash makes the next character a plain symbol, so `\*`, `\=`, `\[` and `\\` can
+//! still be used as symbols.
+//!
+//! When the symbols are given as a list, each element is the pattern of a tape, and any element
+//! that isn't a pattern is the name of a symbol, which can be longer than a single character.
+//!
+//! The patterns are matched by the machine while running, from the most specific transition of
+//! the state, that is, the one matching the fewest combinations of symbols: the one with the
+//! fewest `*`, as `*` matches any symbol, and then the one whose classes have the smallest product
+//! of sizes. An exact transition always wins over a pattern. Transitions as specific as each other
+//! can't match the same symbols, unless the machine is nondeterministic.
+
+use crate::error::Error;
+use crate::turing_machine::Symbols;
+
+/// Pattern of the symbol read in a tape.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pattern {
+  /// A single symbol.
+  Symbol(char),
+  /// Any symbol.
+  Any,
+  /// Any of the symbols of the class, at least two.
+  Class(Vec<char>),
+}
+
+impl Pattern {
+  /// Returns true if the pattern matches the symbol.
+  pub fn matches(&self, x: char) -> bool {
+    match self {
+      | Pattern::Symbol(y) => x == *y,
+      | Pattern::Any => true,
+      | Pattern::Class(y) => y.contains(&x),
+    }
+  }
+
+  /// Returns the symbols named in the pattern, none for `*`.
+  pub fn symbols(&self) -> &[char] {
+    match self {
+      | Pattern::Symbol(x) => std::slice::from_ref(x),
+      | Pattern::Any => &[],
+      | Pattern::Class(x) => x,
+    }
+  }
+
+  /// Returns the pattern matching the symbols matched by both, if any.
+  pub fn intersection(&self, other: &Pattern) -> Option<Pattern> {
+    match (self, other) {
+      | (Pattern::Any, x) | (x, Pattern::Any) => Some(x.clone()),
+      | (x, y) => class(y.symbols().iter().copied().filter(|z| x.matches(*z)).collect()).ok(),
+    }
+  }
+
+  /// Returns the pattern as written in the configuration.
+  pub fn name(&self, symbols: &Symbols) -> String {
+    match self {
+      | Pattern::Symbol(x) => symbols.name(*x),
+      | Pattern::Any => "*".to_string(),
+      | Pattern::Class(x) => format!("[{}]", symbols.format(x)),
+    }
+  }
+}
+
+/// Pattern of the symbol written in a tape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WritePattern {
+  /// A single symbol.
+  Symbol(char),
+  /// The same symbol read in the tape.
+  Same,
+}
+
+impl WritePattern {
+  /// Returns the symbol written, given the symbol read in the tape.
+  pub fn symbol(&self, read: char) -> char {
+    match self {
+      | WritePattern::Symbol(x) => *x,
+      | WritePattern::Same => read,
+    }
+  }
+
+  /// Returns the pattern as written in the configuration.
+  pub fn name(&self, symbols: &Symbols) -> String {
+    match self {
+      | WritePattern::Symbol(x) => symbols.name(*x),
+      | WritePattern::Same => "=".to_string(),
+    }
+  }
+}
+
+/// Returns how specific the patterns of the tapes are, the smallest the most specific: the
+/// ammount of `*`, and the product of the sizes of the classes.
+pub fn specificity(patterns: &[Pattern]) -> (usize, usize) {
+  let any = patterns.iter().filter(|x| **x == Pattern::Any).count();
+  let size = patterns.iter().map(|x| x.symbols().len().max(1)).fold(1, usize::saturating_mul);
+  (any, size)
+}
+
+/// Returns the pattern of a class, without repeated symbols: a single symbol if it only has one,
+/// or an error if it is empty.
+fn class(symbols: Vec<char>) -> Result<Pattern, Error> {
+  let mut unique: Vec<char> = Vec::new();
+  for x in symbols {
+    if !unique.contains(&x) {
+      unique.push(x);
+    }
+  }
+  match unique.as_slice() {
+    | [] => Err(Error::InvalidPattern("[]".to_string())),
+    | [x] => Ok(Pattern::Symbol(*x)),
+    | _ => Ok(Pattern::Class(unique)),
+  }
+}
+
+/// Parse the patterns read in each tape.
+pub fn parse_read(s: &str, symbols: &Symbols) -> Result<Vec<Pattern>, Error> {
+  let mut patterns = Vec::new();
+  let mut chars = s.chars();
+  while let Some(x) = chars.next() {
+    let pattern = match x {
+      | '*' => Pattern::Any,
+      | '=' => return Err(Error::InvalidPattern(s.to_string())),
+      | '[' => {
+        let mut members = Vec::new();
+        loop {
+          match chars.next() {
+            | Some(']') => break,
+            | Some('\\') => match chars.next() {
+              | Some(x) => members.push(symbols.symbol(x)?),
+              | None => return Err(Error::InvalidPattern(s.to_string())),
+            },
+            | Some(x) => members.push(symbols.symbol(x)?),
+            | None => return Err(Error::InvalidPattern(s.to_string())),
+          }
+        }
+        class(members).map_err(|_| Error::InvalidPattern(s.to_string()))?
+      },
+      | '\\' => match chars.next() {
+        | Some(x) => Pattern::Symbol(symbols.symbol(x)?),
+        | None => return Err(Error::InvalidPattern(s.to_string())),
+      },
+      | x => Pattern::Symbol(symbols.symbol(x)?),
+    };
+    patterns.push(pattern);
+  }
+  Ok(patterns)
+}
+
+/// Parse the patterns written in each tape.
+pub fn parse_write(s: &str, symbols: &Symbols) -> Result<Vec<WritePattern>, Error> {
+  let mut patterns = Vec::new();
+  let mut chars = s.chars();
+  while let Some(x) = chars.next() {
+    let pattern = match x {
+      | '=' => WritePattern::Same,
+      | '*' | '[' => return Err(Error::InvalidPattern(s.to_string())),
+      | '\\' => match chars.next() {
+        | Some(x) => WritePattern::Symbol(symbols.symbol(x)?),
+        | None => return Err(Error::InvalidPattern(s.to_string())),
+      },
+      | x => WritePattern::Symbol(symbols.symbol(x)?),
+    };
+    patterns.push(pattern);
+  }
+  Ok(patterns)
+}
+
+/// Parse the pattern read in a tape, given as an element of a list: `*`, a class of single
+/// characters, or the name of a symbol. A leading backslash makes the rest of it a name.
+pub fn parse_read_name(name: &str, symbols: &mut Symbols) -> Result<Pattern, Error> {
+  match name {
+    | "*" => Ok(Pattern::Any),
+    | "=" => Err(Error::InvalidPattern(name.to_string())),
+    | x if x.starts_with('[') => match parse_read(x, symbols)?.as_slice() {
+      | [x] => Ok(x.clone()),
+      | _ => Err(Error::InvalidPattern(name.to_string())),
+    },
+    | x => Ok(Pattern::Symbol(symbols.intern(x.strip_prefix('\\').unwrap_or(x))?)),
+  }
+}
+
+/// Parse the pattern written in a tape, given as an element of a list: `=` or the name of a
+/// symbol. A leading backslash makes the rest of it a name.
+pub fn parse_write_name(name: &str, symbols: &mut Symbols) -> Result<WritePattern, Error> {
+  match name {
+    | "=" => Ok(WritePattern::Same),
+    | "*" => Err(Error::InvalidPattern(name.to_string())),
+    | x if x.starts_with('[') => Err(Error::InvalidPattern(name.to_string())),
+    | x => Ok(WritePattern::Symbol(symbols.intern(x.strip_prefix('\\').unwrap_or(x))?)),
+  }
+}
+
+/// Returns the symbol as written in a string, escaping the characters with a meaning in the
+/// patterns.
+pub fn escape(x: char) -> String {
+  match x {
+    | '*' | '=' | '[' | '\\' => format!("\\{x}"),
+    | x => x.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::pattern::{Pattern, WritePattern, parse_read, parse_write, specificity};
+  use crate::turing_machine::Symbols;
+
+  #[test]
+  fn test_patterns() {
+    let symbols = Symbols::default();
+    let read = parse_read("*[aβ]\\*b[cc]", &symbols).unwrap();
+    assert_eq!(
+      read,
+      vec![
+        Pattern::Any,
+        Pattern::Class(vec!['a', '\0']),
+        Pattern::Symbol('*'),
+        Pattern::Symbol('b'),
+        Pattern::Symbol('c')
+      ]
+    );
+    assert_eq!(specificity(&read), (1, 2));
+    assert!(read[0].matches('z') && read[1].matches('\0') && !read[1].matches('b'));
+    let class = Pattern::Class(vec!['b', 'a', 'c']);
+    assert_eq!(read[1].intersection(&class), Some(Pattern::Symbol('a')));
+    assert_eq!(read[0].intersection(&class), Some(class.clone()));
+    assert_eq!(read[3].intersection(&Pattern::Symbol('a')), None);
+    assert_eq!(read[1].name(&symbols), "[aβ]");
+    assert_eq!(
+      parse_write("=X", &symbols).unwrap(),
+      vec![WritePattern::Same, WritePattern::Symbol('X')]
+    );
+    assert_eq!(parse_read("[ab", &symbols), Err(Error::InvalidPattern("[ab".to_string())));
+    assert_eq!(parse_read("[]", &symbols), Err(Error::InvalidPattern("[]".to_string())));
+    assert_eq!(parse_write("*", &symbols), Err(Error::InvalidPattern("*".to_string())));
+  }
+}
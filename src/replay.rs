@@ -0,0 +1,113 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Replay of a run as a self-contained HTML page.
+//!
+//! The run is dumped as a JSON Lines trace, and each record is embedded in the page along with the
+//! initial tapes. The records only have the symbols written and the heads, so the page rebuilds the
+//! tapes of each step from the initial ones, and draws them with play/pause and a scrubber, using
+//! nothing but its own inline script and style, so it can be opened without network access.
+
+use crate::error::Error;
+use crate::turing_machine::{Dump, TraceFormat, TuringMachine};
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// Page of the replay. The data of the run replaces the `RUN_DATA` placeholder.
+const TEMPLATE: &str = include_str!("replay.html");
+/// Maximum ammount of steps replayed, the rest of the run is left out of the page.
+pub const MAX_REPLAYED: usize = 10000;
+
+/// Writer keeping only the first lines written to it, while counting all of them.
+struct Truncated {
+  /// Content of the lines kept.
+  buffer: Vec<u8>,
+  /// Maximum ammount of lines kept.
+  max: usize,
+  /// Ammount of lines written.
+  lines: usize,
+}
+
+impl Write for Truncated {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    for x in buf {
+      if self.lines < self.max {
+        self.buffer.push(*x);
+      }
+      if *x == b'\n' {
+        self.lines += 1;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Returns the HTML page that replays the run of the string on the machine.
+/// Only the first [`MAX_REPLAYED`] steps are replayed.
+pub fn to_html(tm: &TuringMachine, s: &str) -> Result<String, Error> {
+  replay(tm, s, MAX_REPLAYED)
+}
+
+/// Returns the HTML page that replays up to `max` steps of the run of the string on the machine.
+fn replay(tm: &TuringMachine, s: &str, max: usize) -> Result<String, Error> {
+  tm.check_input(s)?;
+  let symbols = tm.symbols();
+  let initial: Vec<Value> = tm
+    .execution(s)
+    .tapes()
+    .iter()
+    .map(|x| {
+      let cells: Vec<String> = x.cells().iter().map(|x| symbols.name(*x)).collect();
+      json!({ "start": x.start(), "cells": cells, "head": x.head() })
+    })
+    .collect();
+  let mut dump = Truncated { buffer: Vec::new(), max, lines: 0 };
+  let format = TraceFormat::JsonLines { tapes: false };
+  let outcome = tm.run(s, Some(Dump { file: &mut dump, format }))?;
+  let trace = String::from_utf8(dump.buffer).map_err(|x| Error::WriteFile(x.to_string()))?;
+  let steps: Vec<Value> = trace
+    .lines()
+    .map(serde_json::from_str)
+    .collect::<Result<_, _>>()
+    .map_err(|x| Error::WriteFile(x.to_string()))?;
+  let run = json!({
+    "input": s,
+    "initial": tm.state_name(tm.initial()),
+    "tapes": initial,
+    "blank": symbols.glyph(),
+    "steps": steps,
+    "total": dump.lines,
+    "outcome": outcome.to_string(),
+  });
+  // A closing tag inside the data would end the script early.
+  let data = run.to_string().replace("</", "<\\/");
+  Ok(TEMPLATE.replace("RUN_DATA", &data))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::parser;
+  use crate::replay::{replay, to_html};
+
+  #[test]
+  fn test_replay() {
+    let tm = parser::from_file("example/example1.toml").unwrap();
+    let html = to_html(&tm, "aa").unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(!html.contains("RUN_DATA") && !html.contains("http"));
+    assert!(html.contains(r#""outcome":"accepted""#));
+    assert!(html.contains(r#""tapes":[{"cells":["a","a"],"head":0,"start":0}]"#));
+    assert!(html.contains(r#"{"direction":["R"],"heads":[1],"next":"1","read":["a"]"#));
+    // Only the initial tapes are embedded.
+    assert_eq!(html.matches(r#""cells""#).count(), 1);
+    let html = replay(&tm, "aa", 2).unwrap();
+    assert!(html.contains(r#""step":1,"write":["M"]}],"tapes""#));
+    assert!(html.contains(r#""total":3"#));
+  }
+}
@@ -0,0 +1,183 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Test cases with the expected outcome of a Turing machine.
+//!
+//! The test cases are defined as an array of tables, either inside the Turing machine
+//! configuration or in a separate toml file.
+//! ```toml
+//! [[test]]
+//! input = "aabbcc"
+//! expect = "accepted"
+//! output = "XXbbcc"   # Optional, expected content of the output tape.
+//! ```
+
+use crate::error::Error;
+use crate::turing_machine::{Outcome, TuringMachine};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Struct representing the raw, not checked test cases of a toml file.
+#[derive(Debug, Default, Clone, Deserialize)]
+struct RawSuite {
+  #[serde(default)]
+  test: Vec<RawTestCase>,
+}
+
+/// Struct representing a raw, not checked test case.
+#[derive(Debug, Default, Clone, Deserialize)]
+struct RawTestCase {
+  input: String,
+  expect: String,
+  output: Option<String>,
+}
+
+/// A single test case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+  /// String run on the Turing machine.
+  pub input: String,
+  /// Expected outcome of the run.
+  pub expect: Outcome,
+  /// Expected content of the output tape, if it has to be checked.
+  pub output: Option<String>,
+}
+
+/// Result of running a single test case.
+#[derive(Clone, Debug)]
+pub struct TestReport {
+  /// Test case run.
+  pub case: TestCase,
+  /// Outcome of the run, or the error if the input is outside the input alphabet.
+  pub outcome: Result<Outcome, Error>,
+  /// Content of the output tape, if there was a single final configuration, with the symbols
+  /// named as in the machine.
+  pub output: Option<String>,
+}
+
+impl TestReport {
+  /// Returns true if the outcome was the expected one.
+  pub fn outcome_matches(&self) -> bool {
+    self.outcome.as_ref().is_ok_and(|x| x.same_kind(&self.case.expect))
+  }
+
+  /// Returns true if the output tape was the expected one, or if it hadn't to be checked.
+  pub fn output_matches(&self) -> bool {
+    match &self.case.output {
+      | Some(expected) => self.output.as_ref() == Some(expected),
+      | None => true,
+    }
+  }
+
+  /// Returns true if the test case passed.
+  pub fn passed(&self) -> bool {
+    self.outcome_matches() && self.output_matches()
+  }
+}
+
+/// Parse the test cases of a toml file.
+/// Any other key, like the ones of the Turing machine configuration, is ignored.
+pub fn from_str(raw: &str) -> Result<Vec<TestCase>, Error> {
+  let suite: RawSuite = toml::from_str(raw).map_err(|x| Error::Parsing(x.to_string()))?;
+  let mut cases = Vec::new();
+  for test in suite.test {
+    let expect = test.expect.parse()?;
+    cases.push(TestCase { input: test.input, expect, output: test.output });
+  }
+  Ok(cases)
+}
+
+/// Read and parse the test cases of the toml file located in `path`.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<TestCase>, Error> {
+  let raw = fs::read_to_string(path).map_err(|x| Error::OpenFile(x.to_string()))?;
+  from_str(&raw)
+}
+
+/// Run every test case on the Turing machine.
+/// A case whose input isn't valid fails, without stopping the rest.
+pub fn run(tm: &TuringMachine, cases: &[TestCase]) -> Result<Vec<TestReport>, Error> {
+  let mut reports = Vec::new();
+  for case in cases {
+    let (outcome, output) = match tm.run_transducer(&case.input, None) {
+      | Ok((outcome, output)) => (Ok(outcome), output.map(|x| x.render(tm.symbols()))),
+      | Err(x @ (Error::InvalidInput(..) | Error::ReservedInput(..))) => (Err(x), None),
+      | Err(x) => return Err(x),
+    };
+    reports.push(TestReport { case: case.clone(), outcome, output });
+  }
+  Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::parser;
+  use crate::suite::{from_str, run};
+  use crate::turing_machine::Outcome;
+
+  #[test]
+  fn test_run_suite() {
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [1]
+      reject = [2]
+      input_alphabet = "abc"
+
+      [[transition]]
+      from = 0
+      next = 1
+      read = "a"
+      write = "b"
+      direction = "S"
+
+      [[transition]]
+      from = 0
+      next = 2
+      read = "b"
+      write = "b"
+      direction = "S"
+
+      [[test]]
+      input = "a"
+      expect = "accepted"
+      output = "b"
+
+      [[test]]
+      input = "b"
+      expect = "rejected"
+
+      [[test]]
+      input = "c"
+      expect = "accepted"
+
+      [[test]]
+      input = "a"
+      expect = "accepted"
+      output = "a"
+
+      [[test]]
+      input = "d"
+      expect = "rejected"
+    "#;
+    let tm = parser::from_str(config).unwrap();
+    let cases = from_str(config).unwrap();
+    assert_eq!(cases.len(), 5);
+    assert_eq!(cases[1].expect, Outcome::Rejected);
+    let reports = run(&tm, &cases).unwrap();
+    let passed: Vec<bool> = reports.iter().map(|x| x.passed()).collect();
+    assert_eq!(passed, [true, true, false, false, false]);
+    assert!(!reports[2].outcome_matches());
+    assert!(reports[3].outcome_matches() && !reports[3].output_matches());
+    assert_eq!(reports[4].outcome, Err(Error::InvalidInput("d".to_string(), "d".to_string())));
+  }
+
+  #[test]
+  fn test_unknown_outcome() {
+    assert!(from_str("[[test]]\ninput = \"a\"\nexpect = \"maybe\"").is_err());
+    assert!(from_str("[[test]]\ninput = \"a\"\nexpect = \"loop\"").is_ok());
+  }
+}
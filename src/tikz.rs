@@ -0,0 +1,212 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! LaTeX output of a Turing machine, to be pasted into a document.
+//!
+//! The state diagram is a `tikzpicture` of the `automata` library of TikZ, with the states laid
+//! out as in the JFLAP export, and the transitions between the same states merged into a single
+//! edge. The run of a string is a `tabular` with the configuration of each step in the `u q v`
+//! notation: the symbols before the head, the state, and the symbols from the head on, with the
+//! blanks around them left out. The blank is written as `⊔`.
+
+use crate::error::Error;
+use crate::layout;
+use crate::pattern::{Pattern, WritePattern};
+use crate::turing_machine::tape::Tape;
+use crate::turing_machine::{BLANK, Symbols, TuringMachine};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Centimeters of the diagram per pixel of the layout.
+const SCALE: f64 = 0.02;
+
+/// Returns the text escaped to be written in LaTeX.
+fn escape(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for x in text.chars() {
+    match x {
+      | '\\' => escaped.push_str("\\textbackslash{}"),
+      | '~' => escaped.push_str("\\textasciitilde{}"),
+      | '^' => escaped.push_str("\\textasciicircum{}"),
+      | '{' | '}' | '$' | '&' | '#' | '%' | '_' => {
+        escaped.push('\\');
+        escaped.push(x);
+      },
+      | x => escaped.push(x),
+    }
+  }
+  escaped
+}
+
+/// Returns the symbol written in LaTeX.
+fn symbol(symbols: &Symbols, x: char) -> String {
+  if x == BLANK { "$\\sqcup$".to_string() } else { escape(&symbols.name(x)) }
+}
+
+/// Returns the pattern read written in LaTeX.
+fn read_pattern(symbols: &Symbols, x: &Pattern) -> String {
+  match x {
+    | Pattern::Symbol(x) => symbol(symbols, *x),
+    | Pattern::Any => "*".to_string(),
+    | Pattern::Class(x) => {
+      format!("[{}]", x.iter().map(|x| symbol(symbols, *x)).collect::<String>())
+    },
+  }
+}
+
+/// Returns the pattern written in LaTeX.
+fn write_pattern(symbols: &Symbols, x: &WritePattern) -> String {
+  match x {
+    | WritePattern::Symbol(x) => symbol(symbols, *x),
+    | WritePattern::Same => "=".to_string(),
+  }
+}
+
+/// Returns the `automata` diagram of the states and transitions of the machine.
+pub fn diagram(tm: &TuringMachine) -> String {
+  let symbols = tm.symbols();
+  let mut tikz = String::from("% Requires \\usetikzlibrary{automata}\n");
+  tikz.push_str("\\begin{tikzpicture}[->, >=stealth, auto]\n");
+  for (state, (x, y)) in layout::positions(tm).into_iter().enumerate() {
+    let mut style = String::from("state");
+    if state == tm.initial() {
+      style.push_str(", initial");
+    }
+    if tm.acceptance().contains(&state) {
+      style.push_str(", accepting");
+    }
+    let (x, y) = (x as f64 * SCALE, -(y as f64) * SCALE);
+    let name = escape(&tm.state_name(state));
+    writeln!(tikz, "  \\node[{style}] (q{state}) at ({x:.1}, {y:.1}) {{{name}}};").unwrap();
+  }
+  // Label of each edge, a line per transition.
+  let mut edges: BTreeMap<(usize, usize), Vec<String>> = BTreeMap::new();
+  for (state, tr) in tm.pattern_table() {
+    let tapes: Vec<String> = (0..tr.len())
+      .map(|i| {
+        let read = read_pattern(symbols, &tr.read_slice()[i]);
+        let write = write_pattern(symbols, &tr.write_slice()[i]);
+        format!("{read} $\\rightarrow$ {write}, {}", tr.move_slice()[i])
+      })
+      .collect();
+    edges.entry((state, tr.next())).or_default().push(tapes.join(" $|$ "));
+  }
+  for ((from, next), lines) in &edges {
+    // Edges going back and forth between two states are bent, so they don't overlap.
+    let style = if from == next {
+      "[loop above]"
+    } else if edges.contains_key(&(*next, *from)) {
+      "[bend left]"
+    } else {
+      ""
+    };
+    let label = lines.join(" \\\\ ");
+    writeln!(tikz, "  \\path (q{from}) edge{style} node[align=center] {{{label}}} (q{next});")
+      .unwrap();
+  }
+  tikz.push_str("\\end{tikzpicture}\n");
+  tikz
+}
+
+/// Returns a tape in the `u q v` notation, given the state written before the head.
+fn configuration(tape: &Tape, state: &str, symbols: &Symbols) -> String {
+  let cells = tape.cells();
+  let used: Vec<isize> =
+    (0..cells.len()).filter(|i| cells[*i] != BLANK).map(|i| tape.start() + i as isize).collect();
+  let first = used.first().map_or(tape.head(), |x| *x.min(&tape.head()));
+  let last = used.last().map_or(tape.head(), |x| *x.max(&tape.head()));
+  let word = |range: std::ops::Range<isize>| -> String {
+    range.map(|x| symbol(symbols, tape.cell(x))).collect()
+  };
+  let (u, v) = (word(first..tape.head()), word(tape.head()..last + 1));
+  let u = if u.is_empty() { String::new() } else { format!("\\texttt{{{u}}}\\,") };
+  format!("{u}\\textit{{{state}}}\\,\\texttt{{{v}}}")
+}
+
+/// Returns the sequence of configurations of the run of the string, as a `tabular` with a row
+/// per step and a column per tape, ending with the outcome. The run is the one of the simulator,
+/// so in a nondeterministic machine it is the branch that accepted, if any.
+pub fn configurations(tm: &TuringMachine, s: &str) -> Result<String, Error> {
+  let symbols = tm.symbols();
+  let mut tikz = format!("\\begin{{tabular}}{{r{}}}\n", "l".repeat(tm.ntapes()));
+  let header: Vec<String> = (1..=tm.ntapes()).map(|i| format!("Tape {i}")).collect();
+  writeln!(tikz, "  Step & {} \\\\ \\hline", header.join(" & ")).unwrap();
+  let mut step = 0;
+  let outcome = tm.run_configurations(s, |state, tapes| {
+    let state = escape(&tm.state_name(state));
+    let tapes: Vec<String> = tapes.iter().map(|x| configuration(x, &state, symbols)).collect();
+    writeln!(tikz, "  {step} & {} \\\\", tapes.join(" & ")).unwrap();
+    step += 1;
+  })?;
+  writeln!(tikz, "  \\hline\n  \\multicolumn{{{}}}{{l}}{{{outcome}}} \\\\", tm.ntapes() + 1)
+    .unwrap();
+  tikz.push_str("\\end{tabular}\n");
+  Ok(tikz)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::parser;
+  use crate::tikz::{configurations, diagram};
+
+  #[test]
+  fn test_tikz() {
+    let config = r##"
+      ntapes = 1
+      initial = "q_0"
+      accept = ["end"]
+      patterns = true
+      [[transition]]
+      from = "q_0"
+      next = "q_0"
+      read = "[ab]"
+      write = "#"
+      direction = "R"
+      [[transition]]
+      from = "q_0"
+      next = "end"
+      read = "β"
+      write = "β"
+      direction = "L"
+    "##;
+    let tm = parser::from_str(config).unwrap();
+    let tikz = diagram(&tm);
+    assert!(tikz.contains("  \\node[state, initial] (q0) at (2.0, -2.0) {q\\_0};\n"));
+    assert!(tikz.contains("  \\node[state, accepting] (q1) at (5.0, -2.0) {end};\n"));
+    let lines = "{[ab] $\\rightarrow$ \\#, R}";
+    assert!(
+      tikz.contains(&format!("  \\path (q0) edge[loop above] node[align=center] {lines} (q0);"))
+    );
+    let table = configurations(&tm, "ab").unwrap();
+    assert!(table.contains("  0 & \\textit{q\\_0}\\,\\texttt{ab} \\\\\n"));
+    assert!(
+      table.contains("  2 & \\texttt{\\#\\#}\\,\\textit{q\\_0}\\,\\texttt{$\\sqcup$} \\\\\n")
+    );
+    assert!(table.contains("  3 & \\texttt{\\#}\\,\\textit{end}\\,\\texttt{\\#} \\\\\n"));
+    assert!(table.ends_with("\\multicolumn{2}{l}{accepted} \\\\\n\\end{tabular}\n"));
+    // Nondeterministic machines go through the branch that accepted.
+    let config = r#"
+      ntapes = 1
+      initial = 0
+      accept = [2]
+      nondeterministic = true
+      [[transition]]
+      from = 0
+      next = 1
+      read = "a"
+      write = "a"
+      direction = "R"
+      [[transition]]
+      from = 0
+      next = 2
+      read = "a"
+      write = "b"
+      direction = "S"
+    "#;
+    let table = configurations(&parser::from_str(config).unwrap(), "a").unwrap();
+    assert!(table.contains("  1 & \\textit{2}\\,\\texttt{b} \\\\\n"));
+    assert!(table.contains("{accepted}"));
+  }
+}
@@ -0,0 +1,155 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Detection of runs that never halt, by looking for repeated configurations.
+
+use crate::turing_machine::tape::Tape;
+use core::fmt;
+use std::collections::HashMap;
+
+/// Cells behind a head on the frontier of its tape compared to detect translated cycles.
+const WINDOW: isize = 32;
+
+/// Cycle of a run that never halts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cycle {
+  /// Step in which the repeated configuration was first reached.
+  pub entry: usize,
+  /// Ammount of steps between two repetitions.
+  pub period: usize,
+  /// Whether the configuration repeats displaced along the tapes, instead of exactly.
+  pub translated: bool,
+}
+
+impl fmt::Display for Cycle {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let kind = if self.translated { "translated cycle" } else { "cycle" };
+    write!(f, "{kind} of period {} from step {}", self.period, self.entry)
+  }
+}
+
+/// Configuration of a tape, as compared by the detector.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum TapeKey {
+  /// Content trimmed of blanks, and position of the head relative to it.
+  Full(Vec<char>, isize),
+  /// Head on the right or left frontier of the visited cells, with the generation of that
+  /// frontier and the cells behind the head. Every cell beyond the frontier is blank.
+  Frontier(bool, usize, Vec<char>),
+}
+
+/// Configuration of the machine, as compared by the detector.
+type Key = (usize, Vec<TapeKey>);
+
+/// Struct detecting the cycles of a deterministic run, checking the configuration of each step.
+///
+/// Exact cycles are found by hashing the whole configuration of every step. Translated cycles,
+/// where the heads drift forever into blank cells, are found comparing only a window of cells
+/// behind the heads on a frontier. That is only sound while the heads never go further back than
+/// the window, so each time one does, the generation of that frontier changes, invalidating the
+/// configurations recorded before.
+#[derive(Clone, Debug, Default)]
+pub(super) struct CycleDetector {
+  /// Step of each whole configuration reached.
+  seen: HashMap<Key, usize>,
+  /// Step of each windowed configuration reached.
+  frontier: HashMap<Key, usize>,
+  /// Generation of the right and left frontiers of each tape.
+  generations: Vec<[usize; 2]>,
+}
+
+impl CycleDetector {
+  /// Creates a new detector for a run with `ntapes` tapes.
+  pub(super) fn new(ntapes: usize) -> Self {
+    CycleDetector { generations: vec![[0, 0]; ntapes], ..Default::default() }
+  }
+
+  /// Record the configuration of the step, returning the cycle if it repeats a previous one.
+  pub(super) fn check(&mut self, step: usize, state: usize, tapes: &[Tape]) -> Option<Cycle> {
+    let full: Vec<TapeKey> = tapes
+      .iter()
+      .map(|x| {
+        let output = x.output();
+        TapeKey::Full(output.symbols, output.head)
+      })
+      .collect();
+    if let Some(entry) = self.seen.insert((state, full.clone()), step) {
+      return Some(Cycle { entry, period: step - entry, translated: false });
+    }
+    let windowed: Vec<TapeKey> = tapes
+      .iter()
+      .zip(full)
+      .enumerate()
+      .map(|(i, (tape, full))| self.window(i, tape, full))
+      .collect();
+    if windowed.iter().all(|x| matches!(x, TapeKey::Full(..))) {
+      return None;
+    }
+    match self.frontier.insert((state, windowed), step) {
+      | Some(entry) => Some(Cycle { entry, period: step - entry, translated: true }),
+      | None => None,
+    }
+  }
+
+  /// Returns the windowed configuration of the tape, updating the generations of its frontiers.
+  fn window(&mut self, index: usize, tape: &Tape, full: TapeKey) -> TapeKey {
+    let (head, start) = (tape.head(), tape.start());
+    let end = start + tape.size().cast_signed() - 1;
+    let generations = self.generations.get_mut(index).unwrap();
+    if end - head > WINDOW {
+      generations[0] += 1;
+    }
+    if head - start > WINDOW {
+      generations[1] += 1;
+    }
+    if head == end {
+      TapeKey::Frontier(
+        true,
+        generations[0],
+        (head - WINDOW..=head).map(|x| tape.cell(x)).collect(),
+      )
+    } else if head == start {
+      TapeKey::Frontier(
+        false,
+        generations[1],
+        (head..=head + WINDOW).map(|x| tape.cell(x)).collect(),
+      )
+    } else {
+      full
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::turing_machine::{
+    Limits, Outcome, TuringMachine,
+    cycle::Cycle,
+    transition::{Direction, Transition},
+  };
+  use std::collections::HashSet;
+
+  #[test]
+  fn test_cycles() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([3])).unwrap();
+    tm.set_cycle_detection(true);
+    // Bounces between the two first cells when reading an 'a', drifts to the right otherwise.
+    let right = Transition::new(&['a'], &[Direction::Right], 1).unwrap();
+    let left = Transition::new(&['b'], &[Direction::Left], 0).unwrap();
+    let drift = Transition::new(&['X'], &[Direction::Right], 2).unwrap();
+    tm.insert_transition(0, &['a'], &right).unwrap();
+    tm.insert_transition(1, &['b'], &left).unwrap();
+    tm.insert_transition(0, &['c'], &drift).unwrap();
+    tm.insert_transition(2, &['\0'], &drift).unwrap();
+    let exact = Cycle { entry: 0, period: 2, translated: false };
+    assert_eq!(tm.run("ab", None), Ok(Outcome::Loop(exact)));
+    let translated = Cycle { entry: 32, period: 1, translated: true };
+    assert_eq!(tm.run("c", None), Ok(Outcome::Loop(translated)));
+    assert_eq!(tm.run("b", None), Ok(Outcome::HaltedUndefined));
+    tm.set_cycle_detection(false);
+    tm.set_limits(&Limits { steps: 100, ..Default::default() });
+    assert_eq!(tm.run("ab", None), Ok(Outcome::StepLimit));
+  }
+}
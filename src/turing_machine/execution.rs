@@ -0,0 +1,212 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Step by step execution of the Turing machine.
+
+use crate::turing_machine::tape::{Tape, TapeUndo};
+use crate::turing_machine::transition::Transition;
+use crate::turing_machine::{Outcome, TuringMachine};
+use std::borrow::Cow;
+
+/// Record of a single step, needed to undo it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepUndo {
+  /// State before the step.
+  pub state: usize,
+  /// Record of each tape before the step.
+  pub tapes: Vec<TapeUndo>,
+}
+
+/// Struct representing a run that can be advanced step by step, used by interactive front ends.
+/// Each step is recorded in an undo log, so the run can be rewound.
+/// If the machine is nondeterministic, the first transition defined is always the one taken.
+#[derive(Clone, Debug)]
+pub struct Execution<'a> {
+  /// Turing machine being run.
+  tm: &'a TuringMachine,
+  /// String loaded in the first tape.
+  input: String,
+  /// Current state.
+  current: usize,
+  /// Current tapes.
+  tapes: Vec<Tape>,
+  /// Ammount of steps done.
+  steps: usize,
+  /// Undo log, with a record for each step done.
+  history: Vec<StepUndo>,
+}
+
+impl<'a> Execution<'a> {
+  /// Creates a new execution of the Turing machine, loading the string.
+  pub fn new(tm: &'a TuringMachine, s: &str) -> Self {
+    let tapes = tm.load(s);
+    Execution {
+      tm,
+      input: s.to_string(),
+      current: tm.initial,
+      tapes,
+      steps: 0,
+      history: Vec::new(),
+    }
+  }
+
+  /// Returns the Turing machine being run.
+  pub fn machine(&self) -> &'a TuringMachine {
+    self.tm
+  }
+
+  /// Returns the string loaded in the first tape.
+  pub fn input(&self) -> &str {
+    &self.input
+  }
+
+  /// Returns the current state.
+  pub fn state(&self) -> usize {
+    self.current
+  }
+
+  /// Returns the current tapes.
+  pub fn tapes(&self) -> &[Tape] {
+    &self.tapes
+  }
+
+  /// Returns the ammount of steps done.
+  pub fn steps(&self) -> usize {
+    self.steps
+  }
+
+  /// Returns the symbol under the head of each tape.
+  pub fn read(&self) -> Vec<char> {
+    TuringMachine::read_tapes(&self.tapes)
+  }
+
+  /// Returns the transition that will be taken in the next step, if any.
+  pub fn next_transition(&self) -> Option<Cow<'a, Transition>> {
+    if self.tm.final_outcome(self.current).is_some() {
+      return None;
+    }
+    match self.tm.transitions(self.current, &self.read()) {
+      | Cow::Borrowed(x) => x.first().map(Cow::Borrowed),
+      | Cow::Owned(x) => x.into_iter().next().map(Cow::Owned),
+    }
+  }
+
+  /// Returns the outcome if the execution has halted, None otherwise.
+  pub fn outcome(&self) -> Option<Outcome> {
+    match self.tm.final_outcome(self.current) {
+      | Some(x) => Some(x),
+      | None if self.next_transition().is_none() => Some(Outcome::HaltedUndefined),
+      | None => None,
+    }
+  }
+
+  /// Do a single step. Returns false if the execution had already halted, true otherwise.
+  pub fn step(&mut self) -> bool {
+    if self.outcome().is_some() {
+      return false;
+    }
+    let tapes = self.tapes.iter().map(|x| x.undo_record()).collect();
+    self.history.push(StepUndo { state: self.current, tapes });
+    self.steps += 1;
+    self.tm.step(&mut self.current, &mut self.tapes)
+  }
+
+  /// Returns the undo log, with a record for each step done.
+  pub fn history(&self) -> &[StepUndo] {
+    &self.history
+  }
+
+  /// Undo the last step. Returns false if there wasn't any step to undo, true otherwise.
+  pub fn step_back(&mut self) -> bool {
+    match self.history.pop() {
+      | Some(record) => {
+        self.current = record.state;
+        self.tapes.iter_mut().zip(record.tapes.iter()).for_each(|(x, y)| x.undo(y));
+        self.steps -= 1;
+        true
+      },
+      | None => false,
+    }
+  }
+
+  /// Undo up to `n` steps, returning how many have been undone.
+  pub fn rewind(&mut self, n: usize) -> usize {
+    (0..n).take_while(|_| self.step_back()).count()
+  }
+
+  /// Undo steps until the last previous visit of the state.
+  /// Returns false, without undoing any step, if the state hasn't been visited before.
+  pub fn rewind_to_state(&mut self, state: usize) -> bool {
+    match self.history.iter().rposition(|x| x.state == state) {
+      | Some(x) => {
+        self.rewind(self.history.len() - x);
+        true
+      },
+      | None => false,
+    }
+  }
+
+  /// Restart the execution, loading again the string.
+  pub fn restart(&mut self) {
+    *self = Execution::new(self.tm, &self.input);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::turing_machine::{
+    Execution, Outcome, TuringMachine,
+    transition::{Direction, Transition},
+  };
+  use std::collections::HashSet;
+
+  #[test]
+  fn test_execution() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([1])).unwrap();
+    let walk = Transition::new(&['a'], &[Direction::Right], 0).unwrap();
+    let end = Transition::new(&['\0'], &[Direction::Stop], 1).unwrap();
+    tm.insert_transition(0, &['a'], &walk).unwrap();
+    tm.insert_transition(0, &['\0'], &end).unwrap();
+    let mut execution = Execution::new(&tm, "aa");
+    assert_eq!(execution.next_transition().unwrap().next(), 0);
+    assert!(execution.step());
+    assert!(execution.step());
+    assert_eq!(execution.tapes()[0].head(), 2);
+    assert_eq!(execution.outcome(), None);
+    assert!(execution.step());
+    assert_eq!(execution.outcome(), Some(Outcome::Accepted));
+    assert!(!execution.step());
+    assert_eq!(execution.steps(), 3);
+    execution.restart();
+    assert_eq!((execution.state(), execution.steps()), (0, 0));
+  }
+
+  #[test]
+  fn test_rewind() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([2])).unwrap();
+    let mark = Transition::new(&['X'], &[Direction::Right], 1).unwrap();
+    let back = Transition::new(&['Y'], &[Direction::Left], 0).unwrap();
+    let end = Transition::new(&['\0'], &[Direction::Stop], 2).unwrap();
+    tm.insert_transition(0, &['a'], &mark).unwrap();
+    tm.insert_transition(1, &['b'], &back).unwrap();
+    tm.insert_transition(0, &['X'], &mark).unwrap();
+    tm.insert_transition(1, &['Y'], &Transition::new(&['Y'], &[Direction::Right], 1).unwrap())
+      .unwrap();
+    tm.insert_transition(1, &['\0'], &end).unwrap();
+    let mut execution = Execution::new(&tm, "ab");
+    while execution.step() {}
+    assert_eq!(execution.outcome(), Some(Outcome::Accepted));
+    assert_eq!(execution.tapes()[0].to_string(), "|X|Y|[β]|");
+    assert!(execution.step_back());
+    assert_eq!(execution.state(), 1);
+    assert!(execution.rewind_to_state(0));
+    assert_eq!((execution.state(), execution.steps()), (0, 2));
+    assert_eq!(execution.tapes()[0].to_string(), "|[X]|Y|");
+    assert_eq!(execution.rewind(10), 2);
+    assert_eq!(execution.tapes()[0].to_string(), "|[a]|b|");
+    assert!(!execution.step_back());
+    assert!(!execution.rewind_to_state(2));
+  }
+}
@@ -0,0 +1,50 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Limits of each run of the Turing machine.
+
+use crate::turing_machine::Outcome;
+use crate::turing_machine::tape::Tape;
+use std::time::{Duration, Instant};
+
+/// Default maximum ammount of steps a single run can do before being cancelled.
+pub const DEFAULT_MAX_STEPS: usize = 10000000;
+/// The wall-clock timeout is only checked once every this ammount of steps.
+const TIMEOUT_CHECK: usize = 1024;
+
+/// Struct representing the limits a single run can reach before being cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+  /// Maximum ammount of steps.
+  pub steps: usize,
+  /// Maximum wall-clock time, if any.
+  pub timeout: Option<Duration>,
+  /// Maximum ammount of cells, adding up every tape, if any.
+  pub cells: Option<usize>,
+}
+
+impl Default for Limits {
+  fn default() -> Self {
+    Limits { steps: DEFAULT_MAX_STEPS, timeout: None, cells: None }
+  }
+}
+
+impl Limits {
+  /// Returns the outcome if the run exceeded any of the limits, None otherwise.
+  /// `counter` is the ammount of steps done, and `start` the instant the run started.
+  pub fn exceeded(&self, counter: usize, start: &Instant, tapes: &[Tape]) -> Option<Outcome> {
+    if counter >= self.steps {
+      Some(Outcome::StepLimit)
+    } else if self.cells.is_some_and(|x| tapes.iter().map(Tape::size).sum::<usize>() > x) {
+      Some(Outcome::SpaceLimit)
+    } else if counter.is_multiple_of(TIMEOUT_CHECK)
+      && self.timeout.is_some_and(|x| start.elapsed() >= x)
+    {
+      Some(Outcome::Timeout)
+    } else {
+      None
+    }
+  }
+}
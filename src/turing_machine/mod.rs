@@ -5,22 +5,102 @@
 //!
 //! Turing machine struct module.
 
-mod tape;
+mod cycle;
+mod execution;
+mod limits;
+mod nondeterministic;
+mod symbols;
+pub mod tape;
+mod trace;
 pub mod transition;
 
+pub use crate::turing_machine::cycle::Cycle;
+pub use crate::turing_machine::execution::{Execution, StepUndo};
+pub use crate::turing_machine::limits::{DEFAULT_MAX_STEPS, Limits};
+pub use crate::turing_machine::nondeterministic::PathStep;
+pub use crate::turing_machine::symbols::Symbols;
+pub use crate::turing_machine::trace::{Dump, TraceFormat};
+
 use crate::error::Error;
-use crate::turing_machine::tape::Tape;
-use crate::turing_machine::transition::Transition;
+use crate::pattern::{Pattern, WritePattern};
+use crate::turing_machine::cycle::CycleDetector;
+use crate::turing_machine::tape::{Tape, TapeOutput};
+use crate::turing_machine::transition::{PatternTransition, Transition};
+use core::fmt;
 use std::{
+  borrow::Cow,
   collections::{HashMap, HashSet},
-  fs::File,
-  io::Write,
+  str::FromStr,
+  time::Instant,
 };
 
-/// Maximum ammount of steps a single run can do before being cancelled.
-const MAX_STEP: usize = 10000000;
+/// Represents a blank in a tape's cell.
+pub const BLANK: char = '\0';
+/// Name of the blank in the configurations that don't declare it, also used to print it.
+pub const BLANK_REP: char = 'β';
+
+/// Default ammount of configurations a nondeterministic run can explore before being cancelled.
+pub const DEFAULT_BUDGET: usize = 1000000;
+
+/// Possible outcomes of a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+  /// An acceptance state has been entered.
+  Accepted,
+  /// A rejection state has been entered.
+  Rejected,
+  /// The machine halted in a state without a transition for the symbols readed.
+  HaltedUndefined,
+  /// The run has been cancelled after reaching the step limit.
+  StepLimit,
+  /// The run has been cancelled after reaching the wall-clock timeout.
+  Timeout,
+  /// The run has been cancelled after reaching the tape-cell limit.
+  SpaceLimit,
+  /// The run has been cancelled after detecting a cycle, so it would never halt.
+  Loop(Cycle),
+}
+
+impl Outcome {
+  /// Returns true if both outcomes are the same, regardless of the details of the cycle.
+  pub fn same_kind(&self, other: &Outcome) -> bool {
+    std::mem::discriminant(self) == std::mem::discriminant(other)
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      | Outcome::Accepted => write!(f, "accepted"),
+      | Outcome::Rejected => write!(f, "rejected"),
+      | Outcome::HaltedUndefined => write!(f, "halted without a defined transition"),
+      | Outcome::StepLimit => write!(f, "reached the step limit"),
+      | Outcome::Timeout => write!(f, "reached the timeout"),
+      | Outcome::SpaceLimit => write!(f, "reached the tape-cell limit"),
+      | Outcome::Loop(_) => write!(f, "loops forever"),
+    }
+  }
+}
+
+impl FromStr for Outcome {
+  type Err = Error;
 
-/// Struct representing a deterministic Turing machine.
+  /// Parse the outcome from its short name, as used in the test cases.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      | "accepted" => Ok(Outcome::Accepted),
+      | "rejected" => Ok(Outcome::Rejected),
+      | "halted" => Ok(Outcome::HaltedUndefined),
+      | "step-limit" => Ok(Outcome::StepLimit),
+      | "timeout" => Ok(Outcome::Timeout),
+      | "space-limit" => Ok(Outcome::SpaceLimit),
+      | "loop" => Ok(Outcome::Loop(Cycle::default())),
+      | _ => Err(Error::UnknownOutcome(s.to_string())),
+    }
+  }
+}
+
+/// Struct representing a deterministic or nondeterministic Turing machine.
 /// Inside the struct only will be present the definition of the TuringMachine.
 /// The tapes will be independent of each run.
 #[derive(Clone, Debug)]
@@ -30,65 +110,416 @@ pub struct TuringMachine {
   /// Tape number.
   ntapes: usize,
   /// Transition function.
-  function: Vec<HashMap<Vec<char>, Transition>>,
+  function: Vec<HashMap<Vec<char>, Vec<Transition>>>,
+  /// Transitions reading a pattern in some tape, by state, from the most specific.
+  patterns: Vec<Vec<PatternTransition>>,
   /// Set of the final acceptance states.
   acceptance: HashSet<usize>,
+  /// Set of the final rejection states.
+  rejection: HashSet<usize>,
+  /// Whether multiple transitions for the same pair state-readed are allowed.
+  nondeterministic: bool,
+  /// Maximum ammount of configurations explored in a nondeterministic run.
+  budget: usize,
+  /// Human-readable name of each state, indexed by the state.
+  names: Vec<String>,
+  /// Tape used as output when running as a transducer.
+  output: usize,
+  /// Limits of each run.
+  limits: Limits,
+  /// Whether deterministic runs look for cycles, to cancel them if they would never halt.
+  cycles: bool,
+  /// Symbols allowed in the input strings, if declared.
+  input_alphabet: Option<HashSet<char>>,
+  /// Symbols allowed in the transitions, besides the blank, if declared.
+  tape_alphabet: Option<HashSet<char>>,
+  /// Names of the symbols longer than a single character.
+  symbols: Symbols,
 }
 
 impl TuringMachine {
-  /// Creates a new TuringMachine instance.
+  /// Creates a new deterministic TuringMachine instance.
   pub fn new(initial: usize, ntapes: usize, accept: &HashSet<usize>) -> Result<Self, Error> {
     if ntapes == 0 {
       Err(Error::TapeErrorCount)
     } else {
       Ok(TuringMachine {
-        initial: initial,
-        ntapes: ntapes,
+        initial,
+        ntapes,
         function: Vec::new(),
+        patterns: Vec::new(),
         acceptance: accept.clone(),
+        rejection: HashSet::new(),
+        nondeterministic: false,
+        budget: DEFAULT_BUDGET,
+        names: Vec::new(),
+        output: 0,
+        limits: Limits::default(),
+        cycles: false,
+        input_alphabet: None,
+        tape_alphabet: None,
+        symbols: Symbols::default(),
       })
     }
   }
 
-  /// Auxiliar function, that choose what type of run do depending of there is a file to dump or not.
-  pub fn run(&self, s: &str, file: Option<File>) -> Result<bool, Error> {
-    if file.is_some() {
-      let file = file.expect("Unexpected error opening option");
-      self.dump_run(s, file)
-    } else {
-      self.normal_run(s)
+  /// Creates a new nondeterministic TuringMachine instance.
+  /// Each run will explore at most `budget` configurations of the computation tree.
+  pub fn new_nondeterministic(
+    initial: usize, ntapes: usize, accept: &HashSet<usize>, budget: usize,
+  ) -> Result<Self, Error> {
+    let mut tm = Self::new(initial, ntapes, accept)?;
+    tm.nondeterministic = true;
+    tm.budget = budget;
+    Ok(tm)
+  }
+
+  /// Run the string `s` through the machine, returning the outcome of the run.
+  /// The run halts as soon as an acceptance or rejection state is entered.
+  /// If `dump` is present, each step will be written to it, in its trace format.
+  /// In a nondeterministic machine, only the steps of the accepting branch are dumped.
+  pub fn run(&self, s: &str, dump: Option<Dump<'_>>) -> Result<Outcome, Error> {
+    Ok(self.execute(s, dump)?.0)
+  }
+
+  /// Run the string `s` through the machine as a transducer, returning the outcome of the run
+  /// and the final content of the output tape.
+  /// In a nondeterministic machine, the output is only present if some branch accepted.
+  pub fn run_transducer(
+    &self, s: &str, dump: Option<Dump<'_>>,
+  ) -> Result<(Outcome, Option<TapeOutput>), Error> {
+    let (outcome, tapes) = self.execute(s, dump)?;
+    Ok((outcome, tapes.map(|x| x.get(self.output).unwrap().output())))
+  }
+
+  /// Run the string `s` through the machine, returning the outcome of the run and its path.
+  /// A deterministic machine follows its single branch within the limits of the machine, and
+  /// always returns its path. A nondeterministic one explores the computation tree within its
+  /// budget, and only returns the path of the branch that accepted, if any.
+  pub fn run_path(&self, s: &str) -> Result<(Outcome, Option<Vec<PathStep>>), Error> {
+    self.check_input(s)?;
+    if self.nondeterministic {
+      return Ok(self.run_nondeterministic(s, self.budget));
     }
+    let mut path = Vec::new();
+    let (outcome, _) = self.deterministic_run(s, |_, state, read, tr, _| {
+      path.push(PathStep { state, read: read.to_vec(), transition: tr.clone() });
+      Ok(())
+    })?;
+    Ok((outcome, Some(path)))
   }
 
-  /// Run the simulator, loading the string.
-  fn normal_run(&self, s: &str) -> Result<bool, Error> {
-    let mut tapes = vec![Tape::new(); self.ntapes];
-    let mut current: usize = self.initial;
-    let mut counter = 0;
-    tapes.get_mut(0).unwrap().load_string(s);
-    while self.step(&mut current, tapes.as_mut_slice()) {
-      if counter >= MAX_STEP {
-        return Err(Error::MaxStepsReached);
+  /// Run the string `s` through the machine, calling `on_configuration` with the state and the
+  /// tapes at the start and after each step, and returning the outcome of the run.
+  /// In a nondeterministic machine, only the steps of the accepting branch are gone through.
+  pub fn run_configurations(
+    &self, s: &str, mut on_configuration: impl FnMut(usize, &[Tape]),
+  ) -> Result<Outcome, Error> {
+    self.check_input(s)?;
+    let mut tapes = self.load(s);
+    on_configuration(self.initial, &tapes);
+    if self.nondeterministic {
+      let (outcome, path) = self.run_nondeterministic(s, self.budget);
+      for step in path.iter().flatten() {
+        Self::update_tapes(&mut tapes, &step.transition);
+        on_configuration(step.transition.next(), &tapes);
       }
-      counter += 1;
+      return Ok(outcome);
     }
-    Ok(self.acceptance.get(&current).is_some())
+    let (outcome, _) = self.deterministic_run(s, |_, _, _, tr, tapes| {
+      on_configuration(tr.next(), tapes);
+      Ok(())
+    })?;
+    Ok(outcome)
+  }
+
+  /// Choose what type of run do depending of the machine and if there is a file to dump or not.
+  /// Returns the outcome and the final tapes, if there is a single final configuration.
+  fn execute(
+    &self, s: &str, dump: Option<Dump<'_>>,
+  ) -> Result<(Outcome, Option<Vec<Tape>>), Error> {
+    self.check_input(s)?;
+    if self.nondeterministic {
+      let (outcome, path) = self.run_nondeterministic(s, self.budget);
+      return match path {
+        | Some(path) => Ok((outcome, Some(self.replay_path(s, &path, dump)?))),
+        | None => Ok((outcome, None)),
+      };
+    }
+    let (outcome, tapes) = match dump {
+      | Some(mut dump) => self.deterministic_run(s, |counter, state, read, tr, tapes| {
+        self.write_step(counter, state, read, tr, tapes, &mut dump)
+      })?,
+      | None => self.deterministic_run(s, |_, _, _, _, _| Ok(()))?,
+    };
+    Ok((outcome, Some(tapes)))
+  }
+
+  /// Returns the symbols allowed in the input strings, if declared.
+  pub fn input_alphabet(&self) -> Option<&HashSet<char>> {
+    self.input_alphabet.as_ref()
+  }
+
+  /// Set the symbols allowed in the input strings.
+  pub fn set_input_alphabet(&mut self, alphabet: &HashSet<char>) {
+    self.input_alphabet = Some(alphabet.clone());
+  }
+
+  /// Returns the symbols allowed in the transitions, besides the blank, if declared.
+  pub fn tape_alphabet(&self) -> Option<&HashSet<char>> {
+    self.tape_alphabet.as_ref()
+  }
+
+  /// Set the symbols allowed in the transitions, besides the blank.
+  /// Only the transitions inserted afterwards are checked.
+  pub fn set_tape_alphabet(&mut self, alphabet: &HashSet<char>) {
+    self.tape_alphabet = Some(alphabet.clone());
+  }
+
+  /// Returns the names of the symbols longer than a single character.
+  pub fn symbols(&self) -> &Symbols {
+    &self.symbols
+  }
+
+  /// Set the names of the symbols longer than a single character.
+  pub fn set_symbols(&mut self, symbols: Symbols) {
+    self.symbols = symbols;
+  }
+
+  /// Returns an error if the string contains a symbol outside the input alphabet, or a character
+  /// reserved to the symbols longer than a single character. Blanks are always allowed. It is
+  /// checked before every run, but not when creating an Execution.
+  pub fn check_input(&self, s: &str) -> Result<(), Error> {
+    if let Some(x) = s.chars().find(|x| Symbols::is_reserved(*x)) {
+      return Err(Error::ReservedInput(s.to_string(), x));
+    }
+    let Some(alphabet) = &self.input_alphabet else { return Ok(()) };
+    match self.symbols.input(s).iter().find(|x| **x != BLANK && !alphabet.contains(x)) {
+      | Some(x) => Err(Error::InvalidInput(s.to_string(), self.symbols.name(*x))),
+      | None => Ok(()),
+    }
+  }
+
+  /// Returns the tape used as output when running as a transducer.
+  pub fn output_tape(&self) -> usize {
+    self.output
+  }
+
+  /// Set the tape used as output when running as a transducer.
+  pub fn set_output_tape(&mut self, output: usize) -> Result<(), Error> {
+    if output >= self.ntapes {
+      return Err(Error::UnknownTape(output, self.ntapes));
+    }
+    self.output = output;
+    Ok(())
+  }
+
+  /// Returns true if the machine allows multiple transitions for the same pair state-readed.
+  pub fn is_nondeterministic(&self) -> bool {
+    self.nondeterministic
+  }
+
+  /// Returns the ammount of configurations a nondeterministic run can explore.
+  pub fn budget(&self) -> usize {
+    self.budget
+  }
+
+  /// Set the ammount of configurations a nondeterministic run can explore.
+  pub fn set_budget(&mut self, budget: usize) {
+    self.budget = budget;
+  }
+
+  /// Returns the limits of each run.
+  pub fn limits(&self) -> &Limits {
+    &self.limits
+  }
+
+  /// Set the limits of each run.
+  pub fn set_limits(&mut self, limits: &Limits) {
+    self.limits = *limits;
+  }
+
+  /// Returns whether deterministic runs look for cycles.
+  pub fn detects_cycles(&self) -> bool {
+    self.cycles
+  }
+
+  /// Set whether deterministic runs look for cycles, cancelling them with Outcome::Loop.
+  /// Every configuration reached is recorded, so it is expensive on long runs.
+  pub fn set_cycle_detection(&mut self, cycles: bool) {
+    self.cycles = cycles;
+  }
+
+  /// Returns the initial state.
+  pub fn initial(&self) -> usize {
+    self.initial
+  }
+
+  /// Returns the number of tapes.
+  pub fn ntapes(&self) -> usize {
+    self.ntapes
   }
 
-  /// Run the simulator normally, but also dump into a file the state of the tapes.
-  fn dump_run(&self, s: &str, mut file: File) -> Result<bool, Error> {
+  /// Set the human-readable name of each state, indexed by the state.
+  pub fn set_state_names(&mut self, names: &[String]) {
+    self.names = names.to_owned();
+  }
+
+  /// Returns the human-readable name of the state, or its index if it doesn't have one.
+  pub fn state_name(&self, state: usize) -> String {
+    match self.names.get(state) {
+      | Some(x) => x.clone(),
+      | None => state.to_string(),
+    }
+  }
+
+  /// Returns the state with the given human-readable name, if any.
+  pub fn state_index(&self, name: &str) -> Option<usize> {
+    self.names.iter().position(|x| x == name)
+  }
+
+  /// Returns the number of states: one more than the greatest state named, final, initial or
+  /// with transitions.
+  pub fn nstates(&self) -> usize {
+    let finals = self.acceptance.iter().chain(&self.rejection).map(|x| x + 1);
+    let next = self.function.iter().flat_map(|x| x.values().flatten()).map(|x| x.next() + 1);
+    let patterns = self.patterns.iter().flatten().map(|x| x.next() + 1);
+    [self.function.len(), self.patterns.len(), self.names.len(), self.initial + 1]
+      .into_iter()
+      .chain(finals)
+      .chain(next)
+      .chain(patterns)
+      .max()
+      .unwrap()
+  }
+
+  /// Returns the set of acceptance states.
+  pub fn acceptance(&self) -> &HashSet<usize> {
+    &self.acceptance
+  }
+
+  /// Returns the set of rejection states.
+  pub fn rejection(&self) -> &HashSet<usize> {
+    &self.rejection
+  }
+
+  /// Set the rejection states.
+  pub fn set_rejection(&mut self, reject: &HashSet<usize>) {
+    self.rejection = reject.clone();
+  }
+
+  /// Returns the transitions defined for the state and the symbols readed, matching the patterns.
+  /// A deterministic machine will return at most one transition, the most specific one, while a
+  /// nondeterministic one returns every transition matching them, the exact ones first.
+  pub fn transitions(&self, state: usize, read: &[char]) -> Cow<'_, [Transition]> {
+    let exact =
+      self.function.get(state).and_then(|x| x.get(read)).map(|x| x.as_slice()).unwrap_or_default();
+    if !self.nondeterministic && !exact.is_empty() {
+      return Cow::Borrowed(exact);
+    }
+    let mut patterns = self.patterns.get(state).into_iter().flatten().filter(|x| x.matches(read));
+    if !self.nondeterministic {
+      return patterns.next().map_or(Cow::Borrowed(exact), |x| Cow::Owned(vec![x.resolve(read)]));
+    }
+    let patterns: Vec<Transition> = patterns.map(|x| x.resolve(read)).collect();
+    if patterns.is_empty() { Cow::Borrowed(exact) } else { Cow::Owned([exact, &patterns].concat()) }
+  }
+
+  /// Returns the most specific transition reading a pattern that matches the state and the
+  /// symbols readed, if any. It is only taken if there isn't an exact transition for them.
+  pub fn matching_pattern(&self, state: usize, read: &[char]) -> Option<&PatternTransition> {
+    self.patterns.get(state).into_iter().flatten().find(|x| x.matches(read))
+  }
+
+  /// Returns every transition of the machine with its state and symbols readed,
+  /// ordered by state and then by symbols.
+  pub fn transition_table(&self) -> Vec<(usize, &[char], &Transition)> {
+    let mut table: Vec<(usize, &[char], &Transition)> = Vec::new();
+    for (state, map) in self.function.iter().enumerate() {
+      let mut reads: Vec<&Vec<char>> = map.keys().collect();
+      reads.sort();
+      for read in reads {
+        table.extend(map.get(read).unwrap().iter().map(|tr| (state, read.as_slice(), tr)));
+      }
+    }
+    table
+  }
+
+  /// Returns every transition of the machine with its state, as patterns, ordered by state: first
+  /// the exact ones, by the symbols readed, and then the ones reading a pattern, from the most
+  /// specific. So the first one matching a state and some symbols is the one taken.
+  pub fn pattern_table(&self) -> Vec<(usize, PatternTransition)> {
+    let mut table: Vec<(usize, PatternTransition)> = self
+      .transition_table()
+      .into_iter()
+      .map(|(state, read, tr)| {
+        let read: Vec<Pattern> = read.iter().map(|x| Pattern::Symbol(*x)).collect();
+        let write: Vec<WritePattern> =
+          tr.write_slice().iter().map(|x| WritePattern::Symbol(*x)).collect();
+        (state, PatternTransition::new(&read, &write, tr.move_slice(), tr.next()).unwrap())
+      })
+      .collect();
+    for (state, patterns) in self.patterns.iter().enumerate() {
+      table.extend(patterns.iter().map(|x| (state, x.clone())));
+    }
+    table.sort_by_key(|x| x.0);
+    table
+  }
+
+  /// Returns the outcome if the state is a final one, None otherwise.
+  pub fn final_outcome(&self, state: usize) -> Option<Outcome> {
+    if self.acceptance.contains(&state) {
+      Some(Outcome::Accepted)
+    } else if self.rejection.contains(&state) {
+      Some(Outcome::Rejected)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the tapes at the start of a run: the string loaded in the first one, with its blank
+  /// symbols mapped to blanks, and the rest empty.
+  fn load(&self, s: &str) -> Vec<Tape> {
     let mut tapes = vec![Tape::new(); self.ntapes];
+    tapes.get_mut(0).unwrap().load_symbols(&self.symbols.input(s));
+    tapes
+  }
+
+  /// Run the simulator, loading the string.
+  /// `on_step` is called after each step with its number, the state and the symbols readed before
+  /// it, the transition taken and the tapes after it.
+  fn deterministic_run(
+    &self, s: &str,
+    mut on_step: impl FnMut(usize, usize, &[char], &Transition, &[Tape]) -> Result<(), Error>,
+  ) -> Result<(Outcome, Vec<Tape>), Error> {
+    let mut tapes = self.load(s);
     let mut current: usize = self.initial;
     let mut counter = 0;
-    tapes.get_mut(0).unwrap().load_string(s);
-    while self.step(&mut current, tapes.as_mut_slice()) {
-      Self::write_tapes(counter, &tapes, &mut file)?;
-      if counter >= MAX_STEP {
-        return Err(Error::MaxStepsReached);
+    let start = Instant::now();
+    let mut cycles = self.cycles.then(|| CycleDetector::new(self.ntapes));
+    loop {
+      if let Some(outcome) = self.final_outcome(current) {
+        return Ok((outcome, tapes));
+      } else if let Some(outcome) = self.limits.exceeded(counter, &start, &tapes) {
+        return Ok((outcome, tapes));
+      } else if let Some(x) = cycles.as_mut().and_then(|x| x.check(counter, current, &tapes)) {
+        return Ok((Outcome::Loop(x), tapes));
       }
+      let readed = Self::read_tapes(&tapes);
+      let transitions = self.transitions(current, &readed);
+      let Some(tr) = transitions.first() else {
+        return Ok((Outcome::HaltedUndefined, tapes));
+      };
+      Self::update_tapes(&mut tapes, tr);
+      on_step(counter, current, &readed, tr, &tapes)?;
+      current = tr.next();
       counter += 1;
     }
-    Ok(self.acceptance.get(&current).is_some())
+  }
+
+  /// Creates a new execution of the string, that can be advanced step by step.
+  pub fn execution(&self, s: &str) -> Execution<'_> {
+    Execution::new(self, s)
   }
 
   /// Auxiliar function, representing each one of the steps of test().
@@ -96,14 +527,11 @@ impl TuringMachine {
   fn step(&self, current: &mut usize, tapes: &mut [Tape]) -> bool {
     assert!(self.ntapes == tapes.len());
     let readed = Self::read_tapes(tapes);
-    if let Some(x) = self.function.get(*current) {
-      if let Some(x) = x.get(&readed) {
-        *current = x.next();
-        Self::update_tapes(tapes, x);
-        true
-      } else {
-        false
-      }
+    let transitions = self.transitions(*current, &readed);
+    if let Some(x) = transitions.first() {
+      *current = x.next();
+      Self::update_tapes(tapes, x);
+      true
     } else {
       false
     }
@@ -120,30 +548,15 @@ impl TuringMachine {
   fn update_tapes(tapes: &mut [Tape], tr: &Transition) {
     assert!(tapes.len() == tr.len());
     for tape in tapes.iter_mut().enumerate() {
-      let char_to_write = tr.write_slice().get(tape.0).unwrap().clone();
-      let direction_to_move = tr.move_slice().get(tape.0).unwrap().clone();
+      let char_to_write = *tr.write_slice().get(tape.0).unwrap();
+      let direction_to_move = *tr.move_slice().get(tape.0).unwrap();
       tape.1.write(char_to_write);
       tape.1.mov(direction_to_move);
     }
   }
 
-  /// Dump the tapes current state to a file.
-  fn write_tapes(iterat: usize, tapes: &[Tape], file: &mut File) -> Result<(), Error> {
-    let string = format!("Step: {}\n", iterat);
-    if let Err(x) = file.write(string.as_bytes()) {
-      return Err(Error::ErrorWriteFile(x.to_string()));
-    }
-    for tape in tapes.iter().enumerate() {
-      let string = format!("{}: {}\n", tape.0, tape.1.to_string());
-      if let Err(x) = file.write(string.as_bytes()) {
-        return Err(Error::ErrorWriteFile(x.to_string()));
-      }
-    }
-    Ok(())
-  }
-
   /// Add a transition to the Turing machine.
-  /// Due the ammount of Tapes is known compile-time, it will take as parameters an array of
+  /// `read` must contain one symbol per tape, the same as the transition.
   pub fn insert_transition(
     &mut self, state: usize, read: &[char], tr: &Transition,
   ) -> Result<(), Error> {
@@ -152,17 +565,59 @@ impl TuringMachine {
     } else if read.len() != self.ntapes {
       return Err(Error::UnmatchingSizes(self.ntapes, read.len()));
     }
+    if let Some(alphabet) = &self.tape_alphabet
+      && let Some(x) =
+        read.iter().chain(tr.write_slice()).find(|x| **x != BLANK && !alphabet.contains(x))
+    {
+      return Err(Error::UnknownSymbol(self.symbols.name(*x)));
+    }
     self.resize_func_vec(state);
-    if let Some(_) = self.function.get_mut(state).unwrap().insert(read.to_owned(), tr.clone()) {
-      return Err(Error::Indeterminancy);
+    let set = self.function.get_mut(state).unwrap().entry(read.to_owned()).or_default();
+    if !self.nondeterministic && !set.is_empty() {
+      return Err(Error::Indeterminancy(self.state_name(state), self.symbols.format(read)));
     }
+    set.push(tr.clone());
+    Ok(())
+  }
+
+  /// Add a transition reading a pattern in some tape to the Turing machine.
+  /// In a deterministic machine, it can't match the same symbols as another pattern as specific
+  /// as it, as none of them would be taken over the other.
+  pub fn insert_pattern(&mut self, state: usize, tr: &PatternTransition) -> Result<(), Error> {
+    if tr.len() != self.ntapes {
+      return Err(Error::UnmatchingSizes(self.ntapes, tr.len()));
+    }
+    let read = tr.read_slice().iter().flat_map(|x| x.symbols());
+    let write = tr.write_slice().iter().filter_map(|x| match x {
+      | WritePattern::Symbol(x) => Some(x),
+      | WritePattern::Same => None,
+    });
+    if let Some(alphabet) = &self.tape_alphabet
+      && let Some(x) = read.chain(write).find(|x| **x != BLANK && !alphabet.contains(x))
+    {
+      return Err(Error::UnknownSymbol(self.symbols.name(*x)));
+    }
+    if self.patterns.len() <= state {
+      self.patterns.resize_with(state + 1, Vec::new);
+    }
+    let patterns = self.patterns.get_mut(state).unwrap();
+    let specificity = tr.specificity();
+    if !self.nondeterministic
+      && let Some(overlap) =
+        patterns.iter().filter(|x| x.specificity() == specificity).find_map(|x| x.overlap(tr))
+    {
+      let read: String = overlap.iter().map(|x| x.name(&self.symbols)).collect();
+      return Err(Error::Indeterminancy(self.state_name(state), read));
+    }
+    let index = patterns.partition_point(|x| x.specificity() <= specificity);
+    patterns.insert(index, tr.clone());
     Ok(())
   }
 
   /// Resize the function Vector.
   fn resize_func_vec(&mut self, u: usize) {
     if self.function.len() <= u {
-      self.function.resize_with(u + 1, || HashMap::new());
+      self.function.resize_with(u + 1, HashMap::new);
     }
   }
 }
@@ -172,35 +627,125 @@ mod test {
   use std::collections::HashSet;
 
   use crate::turing_machine::{
-    Error, TuringMachine,
+    Error, Limits, Outcome, TuringMachine,
     transition::{Direction, Transition},
   };
+  use std::time::Duration;
 
   #[test]
   fn test_add_transition() {
-    let tr1 =
-      Transition::new(&vec!['a', 'a'], &vec![Direction::Right, Direction::Left], 1).unwrap();
-    let tr2 = Transition::new(
-      &vec!['a', '\0', '\0'],
-      &vec![Direction::Stop, Direction::Right, Direction::Left],
-      3,
-    )
-    .unwrap();
+    let tr1 = Transition::new(&['a', 'a'], &[Direction::Right, Direction::Left], 1).unwrap();
+    let tr2 =
+      Transition::new(&['a', '\0', '\0'], &[Direction::Stop, Direction::Right, Direction::Left], 3)
+        .unwrap();
     let mut tm = TuringMachine::new(0, 2, &HashSet::from([0, 1, 2])).unwrap();
-    assert_eq!(tm.insert_transition(0, &vec!['a', 'a'], &tr1), Ok(()));
-    assert_eq!(tm.insert_transition(10, &vec!['a', 'a'], &tr1), Ok(()));
-    assert_eq!(tm.insert_transition(0, &vec!['b', 'a'], &tr1), Ok(()));
-    assert_eq!(tm.insert_transition(0, &vec!['b', 'a'], &tr1), Err(Error::Indeterminancy));
-    assert_eq!(tm.insert_transition(0, &vec!['a', 'b'], &tr2), Err(Error::UnmatchingSizes(2, 3)));
+    assert_eq!(tm.insert_transition(0, &['a', 'a'], &tr1), Ok(()));
+    assert_eq!(tm.insert_transition(10, &['a', 'a'], &tr1), Ok(()));
+    assert_eq!(tm.insert_transition(0, &['b', 'a'], &tr1), Ok(()));
+    let table: Vec<(usize, Vec<char>)> =
+      tm.transition_table().iter().map(|x| (x.0, x.1.to_vec())).collect();
+    assert_eq!(table, vec![(0, vec!['a', 'a']), (0, vec!['b', 'a']), (10, vec!['a', 'a'])]);
+    assert_eq!(tm.nstates(), 11);
+    assert_eq!(
+      tm.insert_transition(0, &['b', 'a'], &tr1),
+      Err(Error::Indeterminancy("0".to_string(), "ba".to_string()))
+    );
+    assert_eq!(tm.insert_transition(0, &['a', 'b'], &tr2), Err(Error::UnmatchingSizes(2, 3)));
   }
 
   #[test]
   fn test_run_singletape() {
     let mut tm = TuringMachine::new(0, 1, &HashSet::from([0])).unwrap();
-    let tr0 = Transition::new(&vec!['M'], &vec![Direction::Right], 1).unwrap();
-    let tr1 = Transition::new(&vec!['M'], &vec![Direction::Right], 0).unwrap();
-    tm.insert_transition(0, &vec!['a'], &tr0).expect("Unexpected error found adding transition");
-    tm.insert_transition(1, &vec!['a'], &tr1).expect("Unexpected error found adding transition");
-    assert_eq!(tm.run("", None), Ok(true));
+    let tr0 = Transition::new(&['M'], &[Direction::Right], 1).unwrap();
+    let tr1 = Transition::new(&['M'], &[Direction::Right], 0).unwrap();
+    tm.insert_transition(0, &['a'], &tr0).expect("Unexpected error found adding transition");
+    tm.insert_transition(1, &['a'], &tr1).expect("Unexpected error found adding transition");
+    assert_eq!(tm.run("", None), Ok(Outcome::Accepted));
+  }
+
+  #[test]
+  fn test_run_outcomes() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([2])).unwrap();
+    tm.set_rejection(&HashSet::from([3]));
+    let tr_a = Transition::new(&['a'], &[Direction::Right], 2).unwrap();
+    let tr_b = Transition::new(&['b'], &[Direction::Right], 3).unwrap();
+    let tr_leave = Transition::new(&['a'], &[Direction::Right], 0).unwrap();
+    tm.insert_transition(0, &['a'], &tr_a).unwrap();
+    tm.insert_transition(0, &['b'], &tr_b).unwrap();
+    // Final states halt the run even if they have transitions leaving them.
+    tm.insert_transition(2, &['\0'], &tr_leave).unwrap();
+    tm.insert_transition(3, &['\0'], &tr_leave).unwrap();
+    assert_eq!(tm.run("a", None), Ok(Outcome::Accepted));
+    assert_eq!(tm.run("b", None), Ok(Outcome::Rejected));
+    assert_eq!(tm.run("d", None), Ok(Outcome::HaltedUndefined));
+  }
+
+  #[test]
+  fn test_run_limits() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::new()).unwrap();
+    let stay = Transition::new(&['\0'], &[Direction::Stop], 0).unwrap();
+    let right = Transition::new(&['a'], &[Direction::Right], 1).unwrap();
+    tm.insert_transition(0, &['\0'], &stay).unwrap();
+    tm.insert_transition(1, &['\0'], &right).unwrap();
+    tm.set_limits(&Limits { steps: 100, ..Limits::default() });
+    assert_eq!(tm.run("", None), Ok(Outcome::StepLimit));
+    // A zero timeout is already exceeded at the first check, without depending on the clock.
+    tm.set_limits(&Limits { steps: 100, timeout: Some(Duration::ZERO), cells: None });
+    assert_eq!(tm.run("", None), Ok(Outcome::Timeout));
+    tm.set_limits(&Limits { steps: 100, timeout: None, cells: Some(50) });
+    assert_eq!(tm.run("", None), Ok(Outcome::StepLimit));
+    let mut tm = TuringMachine::new(1, 1, &HashSet::new()).unwrap();
+    tm.insert_transition(1, &['\0'], &right).unwrap();
+    tm.set_limits(&Limits { cells: Some(50), ..Limits::default() });
+    assert_eq!(tm.run("", None), Ok(Outcome::SpaceLimit));
+  }
+
+  #[test]
+  fn test_run_path() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([1])).unwrap();
+    let walk = Transition::new(&['a'], &[Direction::Right], 0).unwrap();
+    let end = Transition::new(&['\0'], &[Direction::Stop], 1).unwrap();
+    tm.insert_transition(0, &['a'], &walk).unwrap();
+    tm.insert_transition(0, &['\0'], &end).unwrap();
+    let (outcome, path) = tm.run_path("aa").unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    let states: Vec<(usize, char)> = path.unwrap().iter().map(|x| (x.state, x.read[0])).collect();
+    assert_eq!(states, vec![(0, 'a'), (0, 'a'), (0, '\0')]);
+    // The step limit applies, instead of the budget of the computation tree.
+    tm.set_limits(&Limits { steps: 2, ..Limits::default() });
+    let (outcome, path) = tm.run_path("aa").unwrap();
+    assert_eq!(outcome, Outcome::StepLimit);
+    assert_eq!(path.unwrap().len(), 2);
+  }
+
+  #[test]
+  fn test_run_transducer() {
+    // Unary successor, leaving the head at the start of the number.
+    let mut tm = TuringMachine::new(0, 2, &HashSet::from([2])).unwrap();
+    let walk = Transition::new(&['1', '\0'], &[Direction::Right, Direction::Stop], 0).unwrap();
+    let add = Transition::new(&['1', '\0'], &[Direction::Left, Direction::Stop], 1).unwrap();
+    let back = Transition::new(&['1', '\0'], &[Direction::Left, Direction::Stop], 1).unwrap();
+    let end = Transition::new(&['\0', '\0'], &[Direction::Right, Direction::Stop], 2).unwrap();
+    tm.insert_transition(0, &['1', '\0'], &walk).unwrap();
+    tm.insert_transition(0, &['\0', '\0'], &add).unwrap();
+    tm.insert_transition(1, &['1', '\0'], &back).unwrap();
+    tm.insert_transition(1, &['\0', '\0'], &end).unwrap();
+    let (outcome, output) = tm.run_transducer("11", None).unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    assert_eq!(output.unwrap().to_string(), "111");
+    assert_eq!(tm.set_output_tape(2), Err(Error::UnknownTape(2, 2)));
+    tm.set_output_tape(1).unwrap();
+    let output = tm.run_transducer("11", None).unwrap().1.unwrap();
+    assert!(output.symbols.is_empty());
+  }
+
+  #[test]
+  fn test_add_transition_nondeterministic() {
+    let tr = Transition::new(&['a'], &[Direction::Right], 1).unwrap();
+    let mut tm = TuringMachine::new_nondeterministic(0, 1, &HashSet::from([1]), 100).unwrap();
+    assert_eq!(tm.insert_transition(0, &['a'], &tr), Ok(()));
+    assert_eq!(tm.insert_transition(0, &['a'], &tr), Ok(()));
+    assert_eq!(tm.transitions(0, &['a']).len(), 2);
+    assert!(tm.transitions(1, &['a']).is_empty());
   }
 }
@@ -0,0 +1,142 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Nondeterministic execution of the Turing machine.
+
+use crate::error::Error;
+use crate::turing_machine::tape::Tape;
+use crate::turing_machine::transition::Transition;
+use crate::turing_machine::{Dump, Limits, Outcome, TuringMachine};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Each one of the steps of the branch that accepted a string.
+#[derive(Clone, Debug)]
+pub struct PathStep {
+  /// State before the step.
+  pub state: usize,
+  /// Symbols readed in each tape.
+  pub read: Vec<char>,
+  /// Transition taken.
+  pub transition: Transition,
+}
+
+/// Node of the computation tree, used to rebuild the path once a branch accepts.
+struct Node {
+  /// Index of the parent node, None if it is the root.
+  parent: Option<usize>,
+  /// Step taken from the parent to reach this node.
+  step: Option<PathStep>,
+}
+
+impl TuringMachine {
+  /// Explore the computation tree breadth-first, loading the string.
+  /// Returns Accepted and the path of the first branch entering an acceptance state, if any.
+  /// Otherwise, returns Rejected if every branch entered a rejection state, HaltedUndefined if
+  /// every branch halted but some of them without a defined transition, or StepLimit if more
+  /// than `budget` configurations were explored. The timeout and tape-cell limits of the machine
+  /// are also checked.
+  pub fn run_nondeterministic(&self, s: &str, budget: usize) -> (Outcome, Option<Vec<PathStep>>) {
+    let tapes = self.load(s);
+    let mut nodes = vec![Node { parent: None, step: None }];
+    let mut queue = VecDeque::from([(0, self.initial, tapes)]);
+    let mut counter = 0;
+    let mut outcome = Outcome::Rejected;
+    let limits = Limits { steps: budget, ..self.limits };
+    let start = Instant::now();
+    while let Some((index, current, tapes)) = queue.pop_front() {
+      match self.final_outcome(current) {
+        | Some(Outcome::Accepted) => {
+          return (Outcome::Accepted, Some(Self::build_path(nodes, index)));
+        },
+        | Some(_) => continue,
+        | None => {},
+      }
+      if let Some(outcome) = limits.exceeded(counter, &start, &tapes) {
+        return (outcome, None);
+      }
+      counter += 1;
+      let readed = Self::read_tapes(&tapes);
+      let transitions = self.transitions(current, &readed);
+      if transitions.is_empty() {
+        outcome = Outcome::HaltedUndefined;
+        continue;
+      }
+      for tr in transitions.iter() {
+        let mut next_tapes = tapes.clone();
+        Self::update_tapes(&mut next_tapes, tr);
+        let step = PathStep { state: current, read: readed.clone(), transition: tr.clone() };
+        nodes.push(Node { parent: Some(index), step: Some(step) });
+        queue.push_back((nodes.len() - 1, tr.next(), next_tapes));
+      }
+    }
+    (outcome, None)
+  }
+
+  /// Replay the steps of an accepting path, returning the final tapes.
+  /// If `dump` is present, each step will be written to it, in its trace format.
+  pub(super) fn replay_path(
+    &self, s: &str, path: &[PathStep], mut dump: Option<Dump>,
+  ) -> Result<Vec<Tape>, Error> {
+    let mut tapes = self.load(s);
+    for (counter, step) in path.iter().enumerate() {
+      Self::update_tapes(&mut tapes, &step.transition);
+      if let Some(dump) = dump.as_mut() {
+        self.write_step(counter, step.state, &step.read, &step.transition, &tapes, dump)?;
+      }
+    }
+    Ok(tapes)
+  }
+
+  /// Rebuild the path from the root of the computation tree to the node `index`.
+  fn build_path(mut nodes: Vec<Node>, mut index: usize) -> Vec<PathStep> {
+    let mut path = Vec::new();
+    while let Some(step) = nodes.get_mut(index).unwrap().step.take() {
+      path.push(step);
+      index = nodes.get(index).unwrap().parent.unwrap();
+    }
+    path.reverse();
+    path
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::turing_machine::{
+    Outcome, TuringMachine,
+    transition::{Direction, Transition},
+  };
+  use std::collections::HashSet;
+
+  /// Machine that guesses which one is the last symbol of the string: accepts a^n, n >= 1.
+  fn guessing_machine() -> TuringMachine {
+    let mut tm = TuringMachine::new_nondeterministic(0, 1, &HashSet::from([2]), 1000).unwrap();
+    let keep = Transition::new(&['a'], &[Direction::Right], 0).unwrap();
+    let guess = Transition::new(&['a'], &[Direction::Right], 1).unwrap();
+    let end = Transition::new(&['\0'], &[Direction::Stop], 2).unwrap();
+    tm.insert_transition(0, &['a'], &keep).unwrap();
+    tm.insert_transition(0, &['a'], &guess).unwrap();
+    tm.insert_transition(1, &['\0'], &end).unwrap();
+    tm
+  }
+
+  #[test]
+  fn test_run_nondeterministic() {
+    let tm = guessing_machine();
+    let (outcome, path) = tm.run_nondeterministic("aaa", 1000);
+    let path = path.unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    assert_eq!(path.len(), 4);
+    assert_eq!(path.iter().map(|x| x.state).collect::<Vec<_>>(), vec![0, 0, 0, 1]);
+    assert_eq!(tm.run("aaa", None), Ok(Outcome::Accepted));
+    assert_eq!(tm.run("", None), Ok(Outcome::HaltedUndefined));
+  }
+
+  #[test]
+  fn test_budget_exhausted() {
+    let tm = guessing_machine();
+    assert_eq!(tm.run_nondeterministic("aaaaaaaa", 5).0, Outcome::StepLimit);
+  }
+}
@@ -0,0 +1,192 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Names of the symbols of the tapes.
+//!
+//! Each cell of a tape stores a `char`. Symbols named by a single character are stored as that
+//! character, while longer names, like `X1` or `#0`, are interned to characters of a private use
+//! area of Unicode. That way tapes keep their compact representation, and only printing a symbol
+//! needs to look up its name. The characters of that area are reserved, so they can't be symbols
+//! by themselves, neither in the configuration nor in the input strings.
+//!
+//! Input strings are split into symbols taking at each position the longest name of the blank or
+//! of an interned symbol, and otherwise a single character, so both can be written in them.
+//!
+//! The blank is always stored as [`BLANK`], whatever the name it has in the configuration and the
+//! input strings, and it is printed with its own glyph.
+
+use crate::error::Error;
+use crate::turing_machine::{BLANK, BLANK_REP};
+use std::collections::HashMap;
+
+/// Character of the first interned symbol, at the start of the supplementary private use area A.
+const FIRST: u32 = 0xF0000;
+/// Maximum ammount of interned symbols, the size of the supplementary private use area A.
+const MAX_SYMBOLS: usize = 0xFFFE;
+
+/// Table of the names of the blank and of the symbols longer than a single character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbols {
+  /// Name of the blank in the configuration and the input strings.
+  blank: String,
+  /// How the blank is printed.
+  glyph: String,
+  /// Name of each interned symbol, indexed by its offset from FIRST.
+  names: Vec<String>,
+  /// Interned symbol of each name.
+  ids: HashMap<String, char>,
+}
+
+impl Default for Symbols {
+  fn default() -> Self {
+    let blank = BLANK_REP.to_string();
+    Symbols { glyph: blank.clone(), blank, names: Vec::new(), ids: HashMap::new() }
+  }
+}
+
+impl Symbols {
+  /// Set the name of the blank, and how it is printed, being its own name by default.
+  /// It must be set before interning any symbol.
+  pub fn set_blank(&mut self, name: &str, glyph: Option<&str>) -> Result<(), Error> {
+    if name.is_empty() {
+      return Err(Error::InvalidSymbol(name.to_string()));
+    }
+    self.blank = name.to_string();
+    self.glyph = glyph.unwrap_or(name).to_string();
+    Ok(())
+  }
+
+  /// Returns the name of the blank in the configuration and the input strings.
+  pub fn blank(&self) -> &str {
+    &self.blank
+  }
+
+  /// Returns how the blank is printed.
+  pub fn glyph(&self) -> &str {
+    &self.glyph
+  }
+
+  /// Returns true if the character is reserved to the interned symbols.
+  pub fn is_reserved(x: char) -> bool {
+    x as u32 >= FIRST
+  }
+
+  /// Returns the symbol with the given name, interning it if it wasn't yet.
+  pub fn intern(&mut self, name: &str) -> Result<char, Error> {
+    if let Some(x) = self.get(name) {
+      return Ok(x);
+    }
+    let mut chars = name.chars();
+    if let (Some(x), None) = (chars.next(), chars.next()) {
+      return self.symbol(x);
+    }
+    if name.is_empty() {
+      return Err(Error::InvalidSymbol(name.to_string()));
+    }
+    if self.names.len() >= MAX_SYMBOLS {
+      return Err(Error::SymbolLimit(MAX_SYMBOLS));
+    }
+    let id = char::from_u32(FIRST + self.names.len() as u32).unwrap();
+    self.names.push(name.to_string());
+    self.ids.insert(name.to_string(), id);
+    Ok(id)
+  }
+
+  /// Returns the symbol with the given name, if it is the blank, a single character not reserved
+  /// or it was interned.
+  pub fn get(&self, name: &str) -> Option<char> {
+    if name == self.blank {
+      return Some(BLANK);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+      | (Some(x), None) => self.symbol(x).ok(),
+      | _ => self.ids.get(name).copied(),
+    }
+  }
+
+  /// Returns the symbol named by a single character, mapping the blank name to blanks.
+  /// The reserved characters are an error.
+  pub fn symbol(&self, x: char) -> Result<char, Error> {
+    if Self::is_reserved(x) {
+      Err(Error::InvalidSymbol(x.to_string()))
+    } else if self.blank.chars().eq([x]) {
+      Ok(BLANK)
+    } else {
+      Ok(x)
+    }
+  }
+
+  /// Returns the symbols of an input string, taking at each position the longest name of the
+  /// blank or of an interned symbol, and otherwise a single character.
+  pub fn input(&self, s: &str) -> Vec<char> {
+    let names = self.ids.iter().map(|(name, x)| (name.as_str(), *x));
+    let names: Vec<(&str, char)> = names.chain([(self.blank.as_str(), BLANK)]).collect();
+    let mut symbols = Vec::new();
+    let mut rest = s;
+    while let Some(x) = rest.chars().next() {
+      let longest = names.iter().filter(|y| rest.starts_with(y.0)).max_by_key(|y| y.0.len());
+      let (len, symbol) = longest.map_or((x.len_utf8(), x), |y| (y.0.len(), y.1));
+      symbols.push(symbol);
+      rest = &rest[len..];
+    }
+    symbols
+  }
+
+  /// Returns the name of the symbol. The blank is named by its glyph.
+  pub fn name(&self, x: char) -> String {
+    let interned = (x as u32).checked_sub(FIRST).and_then(|i| self.names.get(i as usize));
+    match interned {
+      | Some(name) => name.clone(),
+      | None if x == BLANK => self.glyph.clone(),
+      | None => x.to_string(),
+    }
+  }
+
+  /// Returns the names of the symbols, one after another.
+  pub fn format(&self, x: &[char]) -> String {
+    x.iter().map(|x| self.name(*x)).collect()
+  }
+
+  /// Returns true if there isn't any interned symbol.
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::turing_machine::symbols::Symbols;
+
+  #[test]
+  fn test_symbols() {
+    let mut symbols = Symbols::default();
+    assert_eq!(symbols.intern("a"), Ok('a'));
+    assert_eq!(symbols.intern("β"), Ok('\0'));
+    let x1 = symbols.intern("X1").unwrap();
+    let q = symbols.intern("q_a").unwrap();
+    assert_ne!(x1, q);
+    assert_eq!(symbols.intern("X1"), Ok(x1));
+    assert_eq!(symbols.get("q_a"), Some(q));
+    assert_eq!(symbols.get("X2"), None);
+    assert_eq!(symbols.format(&['a', x1, '\0', q]), "aX1βq_a");
+    assert_eq!(symbols.intern(""), Err(Error::InvalidSymbol(String::new())));
+    // The characters of the interned symbols can't be symbols by themselves.
+    assert_eq!(symbols.intern(&x1.to_string()), Err(Error::InvalidSymbol(x1.to_string())));
+    assert_eq!(symbols.get(&q.to_string()), None);
+    // The input is split by the longest name found at each position.
+    symbols.intern("X").unwrap();
+    assert_eq!(symbols.input("X1Xq_a1"), vec![x1, 'X', q, '1']);
+    let mut symbols = Symbols::default();
+    symbols.set_blank("_", Some("□")).unwrap();
+    assert_eq!((symbols.intern("_"), symbols.intern("β")), (Ok('\0'), Ok('β')));
+    assert_eq!(symbols.input("a_b"), vec!['a', '\0', 'b']);
+    assert_eq!(symbols.format(&['a', '\0']), "a□");
+    assert_eq!(symbols.set_blank("", None), Err(Error::InvalidSymbol(String::new())));
+    symbols.set_blank("B0", None).unwrap();
+    assert_eq!(symbols.input("aB0B"), vec!['a', '\0', 'B']);
+  }
+}
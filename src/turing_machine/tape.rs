@@ -8,9 +8,45 @@
 use core::fmt;
 use std::ops::{Neg, Sub};
 
-use crate::parser::{BLANK, BLANK_REP};
+use crate::turing_machine::BLANK;
+use crate::turing_machine::symbols::Symbols;
 use crate::turing_machine::transition::Direction;
 
+/// Content of a tape once a run has finished, trimmed of blanks on both sides.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TapeOutput {
+  /// Symbols between the first and the last non blank cells.
+  pub symbols: Vec<char>,
+  /// Position of the head, relative to the first symbol.
+  pub head: isize,
+}
+
+impl TapeOutput {
+  /// Returns the symbols as a string, naming them with the table of the machine.
+  pub fn render(&self, symbols: &Symbols) -> String {
+    symbols.format(&self.symbols)
+  }
+}
+
+impl fmt::Display for TapeOutput {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.render(&Symbols::default()))
+  }
+}
+
+/// Record of a tape before a step, needed to undo the write and the move of the head.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TapeUndo {
+  /// Relative position of the head.
+  head: isize,
+  /// Symbol under the head, before being overwritten.
+  symbol: char,
+  /// Size of the negative half.
+  n_len: usize,
+  /// Size of the positive half.
+  p_len: usize,
+}
+
 /// Struct representing a Single tape.
 #[derive(Clone, Debug)]
 pub struct Tape {
@@ -30,12 +66,17 @@ impl Tape {
 
   /// Loads a string to the tape and reset the tape.
   pub fn load_string(&mut self, f: &str) {
+    self.load_symbols(&f.chars().collect::<Vec<char>>());
+  }
+
+  /// Loads the symbols to the tape and reset the tape.
+  pub fn load_symbols(&mut self, f: &[char]) {
     self.n_half.clear();
     // The tape must have atleast one cell defined to work.
     if f.is_empty() {
-      self.p_half = "\0".chars().collect();
+      self.p_half = vec![BLANK];
     } else {
-      self.p_half = f.chars().collect();
+      self.p_half = f.to_vec();
     }
     self.head = 0;
   }
@@ -72,12 +113,96 @@ impl Tape {
     }
   }
 
+  /// Returns the relative position of the head.
+  pub fn head(&self) -> isize {
+    self.head
+  }
+
+  /// Returns the relative position of the first cell of the tape.
+  pub fn start(&self) -> isize {
+    self.n_half.len().cast_signed().neg()
+  }
+
+  /// Returns the symbol of the cell at the relative position, blank if it hasn't been visited.
+  pub fn cell(&self, pos: isize) -> char {
+    let cell = if pos >= 0 {
+      self.p_half.get(pos.cast_unsigned())
+    } else {
+      self.n_half.get((pos + 1).neg().cast_unsigned())
+    };
+    cell.copied().unwrap_or(BLANK)
+  }
+
+  /// Returns the symbol of every cell of the tape, from left to right.
+  pub fn cells(&self) -> Vec<char> {
+    self.n_half.iter().rev().chain(self.p_half.iter()).copied().collect()
+  }
+
+  /// Returns the content of the tape trimmed of blanks, and the head position relative to it.
+  pub fn output(&self) -> TapeOutput {
+    let start = self.start();
+    let cells = self.cells();
+    match cells.iter().position(|x| *x != BLANK) {
+      | Some(first) => {
+        let last = cells.iter().rposition(|x| *x != BLANK).unwrap();
+        let head = self.head - start - first.cast_signed();
+        TapeOutput { symbols: cells[first..=last].to_vec(), head }
+      },
+      | None => TapeOutput { symbols: Vec::new(), head: 0 },
+    }
+  }
+
+  /// Returns the record needed to undo the next write and move of the head.
+  pub fn undo_record(&self) -> TapeUndo {
+    TapeUndo {
+      head: self.head,
+      symbol: self.read(),
+      n_len: self.n_half.len(),
+      p_len: self.p_half.len(),
+    }
+  }
+
+  /// Restore the tape to the state it had when the record was taken, undoing a write and a move.
+  pub fn undo(&mut self, record: &TapeUndo) {
+    self.n_half.truncate(record.n_len);
+    self.p_half.truncate(record.p_len);
+    self.head = record.head;
+    self.write(record.symbol);
+  }
+
   /// Returns the size of the Tape, being the the sum of both halfs.
-  #[allow(unused)]
   pub fn size(&self) -> usize {
     self.n_half.len() + self.p_half.len()
   }
 
+  /// Returns a visual representation of the tape, naming the symbols with the table of the
+  /// machine. The cells are separated by bars, and the one under the head is inside brackets.
+  pub fn render(&self, symbols: &Symbols) -> String {
+    let mut string = String::new();
+    for x in self.n_half.iter().enumerate() {
+      // From the absolute position in the n_half, get the relative position.
+      // absolute position = 2 relative position = -3
+      //   relative pos = - (n_half.len() - absolute_pos)
+      let relative_pos = (self.n_half.len().cast_signed() - x.0.cast_signed()).neg();
+      if relative_pos == self.head {
+        string.push_str(&format!("|[{}]", symbols.name(*x.1)));
+      } else {
+        string.push_str(&format!("|{}", symbols.name(*x.1)));
+      }
+    }
+    for x in self.p_half.iter().enumerate() {
+      // Absolute position coincide with relative position.
+      let relative_pos = x.0.cast_signed();
+      if relative_pos == self.head {
+        string.push_str(&format!("|[{}]", symbols.name(*x.1)));
+      } else {
+        string.push_str(&format!("|{}", symbols.name(*x.1)));
+      }
+    }
+    string.push('|');
+    string
+  }
+
   /// Clean the current state of the object.
   #[allow(unused)]
   fn clean(&mut self) {
@@ -114,37 +239,16 @@ impl Tape {
   }
 }
 
-impl fmt::Display for Tape {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    for x in self.n_half.iter().enumerate() {
-      // From the absolute position in the n_half, get the relative position.
-      // absolute position = 2 relative position = -3
-      //   relative pos = - (n_half.len() - absolute_pos)
-      let relative_pos = (self.n_half.len().cast_signed() - x.0.cast_signed()).neg();
-      if relative_pos == self.head {
-        write!(f, "|[{}]", print_sym(*x.1))?;
-      } else {
-        write!(f, "|{}", print_sym(*x.1))?;
-      }
-    }
-    for x in self.p_half.iter().enumerate() {
-      // Absolute position coincide with relative position.
-      let relative_pos = x.0.cast_signed();
-      if relative_pos == self.head {
-        write!(f, "|[{}]", print_sym(*x.1))?;
-      } else {
-        write!(f, "|{}", print_sym(*x.1))?;
-      }
-    }
-    write!(f, "|")?;
-    Ok(())
+impl Default for Tape {
+  fn default() -> Self {
+    Self::new()
   }
 }
 
-/// Auxiliar function for Display: returns a visual representation of a symbol.
-/// Basically prints β if the symbol is a blank, itself otherwise.
-fn print_sym(x: char) -> char {
-  if x == BLANK { BLANK_REP } else { x }
+impl fmt::Display for Tape {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.render(&Symbols::default()))
+  }
 }
 
 #[cfg(test)]
@@ -191,6 +295,47 @@ mod tests {
     assert_eq!(x.read(), BLANK);
   }
 
+  #[test]
+  fn test_output() {
+    let mut x = Tape::new();
+    x.load_string("ab");
+    x.move_left();
+    x.move_left();
+    x.write('c');
+    let output = x.output();
+    assert_eq!(output.symbols, vec!['c', BLANK, 'a', 'b']);
+    assert_eq!(output.head, 0);
+    assert_eq!(output.to_string(), "cβab");
+    assert_eq!((x.cell(-2), x.cell(-1), x.cell(1), x.cell(10)), ('c', BLANK, 'b', BLANK));
+    x.move_right();
+    x.move_right();
+    x.move_right();
+    x.move_right();
+    x.move_right();
+    assert_eq!(x.output().head, 5);
+    assert_eq!(Tape::new().output().symbols, vec![]);
+  }
+
+  #[test]
+  fn test_undo() {
+    let mut x = Tape::new();
+    x.load_string("ab");
+    x.move_right();
+    let record = x.undo_record();
+    x.write('c');
+    x.move_right();
+    x.write('d');
+    x.move_right();
+    assert_eq!(x.to_string(), "|a|c|d|[β]|");
+    x.undo(&record);
+    assert_eq!(x.to_string(), "|a|[b]|");
+    let record = x.undo_record();
+    x.move_left();
+    x.move_left();
+    x.undo(&record);
+    assert_eq!(x.to_string(), "|a|[b]|");
+  }
+
   #[test]
   fn test_display() {
     let mut x = Tape::new();
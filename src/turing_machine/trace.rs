@@ -0,0 +1,138 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Formats used to dump the execution trace of a run.
+
+use crate::error::Error;
+use crate::turing_machine::TuringMachine;
+use crate::turing_machine::tape::Tape;
+use crate::turing_machine::transition::Transition;
+use serde::Serialize;
+use std::io::Write;
+
+/// Format of the execution trace written while dumping a run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraceFormat {
+  /// Human-readable text, with the state of the tapes after each step.
+  #[default]
+  Text,
+  /// One JSON object per step, optionally with the full content of the tapes.
+  JsonLines { tapes: bool },
+}
+
+/// Writer where the execution trace of a run is dumped, with the format used.
+pub struct Dump<'a> {
+  /// Writer of the trace.
+  pub file: &'a mut dyn Write,
+  /// Format of the trace.
+  pub format: TraceFormat,
+}
+
+/// Record of a single step, in the JSON Lines format.
+#[derive(Serialize)]
+struct TraceRecord {
+  /// Number of the step, starting at 0.
+  step: usize,
+  /// State before the step.
+  state: String,
+  /// State after the step.
+  next: String,
+  /// Symbol readed in each tape.
+  read: Vec<String>,
+  /// Symbol written in each tape.
+  write: Vec<String>,
+  /// Direction moved in each tape.
+  direction: Vec<String>,
+  /// Position of the head of each tape after the step.
+  heads: Vec<isize>,
+  /// Full content of each tape after the step.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  tapes: Option<Vec<TraceTape>>,
+}
+
+/// Full content of a tape, in the JSON Lines format.
+#[derive(Serialize)]
+struct TraceTape {
+  /// Position of the first cell.
+  start: isize,
+  /// Symbol of each cell.
+  cells: Vec<String>,
+}
+
+impl TuringMachine {
+  /// Dump a step, in the trace format of the dump.
+  /// `state` and `read` are the state and the symbols readed before taking the transition.
+  pub(super) fn write_step(
+    &self, iterat: usize, state: usize, read: &[char], tr: &Transition, tapes: &[Tape],
+    dump: &mut Dump,
+  ) -> Result<(), Error> {
+    match dump.format {
+      | TraceFormat::Text => self.write_tapes(iterat, tapes, dump.file),
+      | TraceFormat::JsonLines { tapes: full } => {
+        let record = TraceRecord {
+          step: iterat,
+          state: self.state_name(state),
+          next: self.state_name(tr.next()),
+          read: read.iter().map(|x| self.symbols.name(*x)).collect(),
+          write: tr.write_slice().iter().map(|x| self.symbols.name(*x)).collect(),
+          direction: tr.move_slice().iter().map(|x| x.to_string()).collect(),
+          heads: tapes.iter().map(|x| x.head()).collect(),
+          tapes: full.then(|| {
+            let cells = |x: &Tape| x.cells().iter().map(|x| self.symbols.name(*x)).collect();
+            tapes.iter().map(|x| TraceTape { start: x.start(), cells: cells(x) }).collect()
+          }),
+        };
+        let mut line =
+          serde_json::to_string(&record).map_err(|x| Error::WriteFile(x.to_string()))?;
+        line.push('\n');
+        dump.file.write_all(line.as_bytes()).map_err(|x| Error::WriteFile(x.to_string()))
+      },
+    }
+  }
+
+  /// Dump the tapes current state to a file.
+  fn write_tapes(&self, iterat: usize, tapes: &[Tape], file: &mut dyn Write) -> Result<(), Error> {
+    let string = format!("Step: {}\n", iterat);
+    if let Err(x) = file.write_all(string.as_bytes()) {
+      return Err(Error::WriteFile(x.to_string()));
+    }
+    for tape in tapes.iter().enumerate() {
+      let string = format!("{}: {}\n", tape.0, tape.1.render(&self.symbols));
+      if let Err(x) = file.write_all(string.as_bytes()) {
+        return Err(Error::WriteFile(x.to_string()));
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::turing_machine::{
+    Dump, TraceFormat, TuringMachine,
+    transition::{Direction, Transition},
+  };
+  use std::collections::HashSet;
+
+  #[test]
+  fn test_json_lines() {
+    let mut tm = TuringMachine::new(0, 1, &HashSet::from([1])).unwrap();
+    let tr = Transition::new(&['\0'], &[Direction::Right], 1).unwrap();
+    tm.insert_transition(0, &['a'], &tr).unwrap();
+    let mut dump = Vec::new();
+    let format = TraceFormat::JsonLines { tapes: true };
+    tm.run("a", Some(Dump { file: &mut dump, format })).unwrap();
+    let expected = concat!(
+      r#"{"step":0,"state":"0","next":"1","read":["a"],"write":["β"],"direction":["R"],"#,
+      r#""heads":[1],"tapes":[{"start":0,"cells":["β","β"]}]}"#,
+      "\n"
+    );
+    assert_eq!(String::from_utf8(dump).unwrap(), expected);
+    // The text format only has the step and the tapes.
+    let mut dump = Vec::new();
+    tm.run("a", Some(Dump { file: &mut dump, format: TraceFormat::Text })).unwrap();
+    assert_eq!(String::from_utf8(dump).unwrap(), "Step: 0\n0: |β|[β]|\n");
+  }
+}
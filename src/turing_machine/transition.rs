@@ -6,6 +6,8 @@
 //! Single transition struct module.
 
 use crate::error::Error;
+use crate::pattern::{self, Pattern, WritePattern};
+use core::fmt;
 
 /// Each transition of the turing machine.
 #[derive(Clone, Debug)]
@@ -47,6 +49,96 @@ impl Transition {
   pub fn len(&self) -> usize {
     self.to_move.len()
   }
+
+  /// Returns true if the transition doesn't act over any tape.
+  pub fn is_empty(&self) -> bool {
+    self.to_move.is_empty()
+  }
+}
+
+/// Transition reading a pattern in some tape, matched against the symbols read while running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternTransition {
+  // Vector of the pattern to read in each tape.
+  to_read: Vec<Pattern>,
+  // Vector of the pattern to write in each tape.
+  to_write: Vec<WritePattern>,
+  // Vector of the Direction to move in each tape.
+  to_move: Vec<Direction>,
+  // The next state.
+  next: usize,
+}
+
+impl PatternTransition {
+  /// Returns a new PatternTransition.
+  /// If the size of tr, tw and tm doesn't match, returns an error.
+  pub fn new(
+    tr: &[Pattern], tw: &[WritePattern], tm: &[Direction], n: usize,
+  ) -> Result<Self, Error> {
+    if tw.len() != tm.len() {
+      return Err(Error::TransitionSizeUnmatch(tw.len(), tm.len()));
+    } else if tr.len() != tm.len() {
+      return Err(Error::TransitionSizeUnmatch(tr.len(), tm.len()));
+    }
+    Ok(PatternTransition {
+      to_read: tr.to_owned(),
+      to_write: tw.to_owned(),
+      to_move: tm.to_owned(),
+      next: n,
+    })
+  }
+
+  /// Returns the next state.
+  pub fn next(&self) -> usize {
+    self.next
+  }
+
+  /// Returns a slice of the patterns to read.
+  pub fn read_slice(&self) -> &[Pattern] {
+    &self.to_read
+  }
+
+  /// Returns a slice of the patterns to write.
+  pub fn write_slice(&self) -> &[WritePattern] {
+    &self.to_write
+  }
+
+  /// Returns a slice of the movements.
+  pub fn move_slice(&self) -> &[Direction] {
+    &self.to_move
+  }
+
+  /// Returns the number of tapes this transition represents.
+  pub fn len(&self) -> usize {
+    self.to_move.len()
+  }
+
+  /// Returns true if the transition doesn't act over any tape.
+  pub fn is_empty(&self) -> bool {
+    self.to_move.is_empty()
+  }
+
+  /// Returns how specific the patterns read are, the smallest the most specific.
+  pub fn specificity(&self) -> (usize, usize) {
+    pattern::specificity(&self.to_read)
+  }
+
+  /// Returns true if the patterns match the symbols read.
+  pub fn matches(&self, read: &[char]) -> bool {
+    self.to_read.iter().zip(read).all(|(x, y)| x.matches(*y))
+  }
+
+  /// Returns the patterns matching the symbols matched by both transitions, if any.
+  pub fn overlap(&self, other: &PatternTransition) -> Option<Vec<Pattern>> {
+    self.to_read.iter().zip(&other.to_read).map(|(x, y)| x.intersection(y)).collect()
+  }
+
+  /// Returns the transition taken when reading the symbols, writing back the ones read where the
+  /// pattern is `=`.
+  pub fn resolve(&self, read: &[char]) -> Transition {
+    let to_write = self.to_write.iter().zip(read).map(|(x, y)| x.symbol(*y)).collect();
+    Transition { to_write, to_move: self.to_move.clone(), next: self.next }
+  }
 }
 
 /// Simple enum representing the possible movements in each transition.
@@ -57,15 +149,38 @@ pub enum Direction {
   Stop,
 }
 
+impl fmt::Display for Direction {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      | Direction::Left => write!(f, "L"),
+      | Direction::Right => write!(f, "R"),
+      | Direction::Stop => write!(f, "S"),
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::turing_machine::transition::{Direction, Transition};
+  use crate::pattern::{Pattern, WritePattern};
+  use crate::turing_machine::transition::{Direction, PatternTransition, Transition};
 
   #[test]
   fn test_transition() {
-    assert!(Transition::new(&vec!['a', 'b'], &vec![Direction::Left, Direction::Right], 2).is_ok());
-    assert!(
-      Transition::new(&vec!['a', 'b', 'c'], &vec![Direction::Left, Direction::Right], 2).is_err()
-    );
+    assert!(Transition::new(&['a', 'b'], &[Direction::Left, Direction::Right], 2).is_ok());
+    assert!(Transition::new(&['a', 'b', 'c'], &[Direction::Left, Direction::Right], 2).is_err());
+  }
+
+  #[test]
+  fn test_pattern_transition() {
+    let read = [Pattern::Any, Pattern::Class(vec!['a', 'b'])];
+    let write = [WritePattern::Same, WritePattern::Symbol('X')];
+    let tr =
+      PatternTransition::new(&read, &write, &[Direction::Right, Direction::Stop], 1).unwrap();
+    assert!(tr.matches(&['z', 'b']) && !tr.matches(&['z', 'c']));
+    assert_eq!(tr.resolve(&['z', 'b']).write_slice(), &['z', 'X']);
+    let other = [Pattern::Symbol('a'), Pattern::Any];
+    let other = PatternTransition::new(&other, &write, &[Direction::Left, Direction::Left], 0);
+    assert_eq!(tr.overlap(&other.unwrap()), Some(vec![Pattern::Symbol('a'), read[1].clone()]));
+    assert!(PatternTransition::new(&read[..1], &write, &[Direction::Left; 2], 0).is_err());
   }
 }
@@ -0,0 +1,276 @@
+//! Author: TheLazyFerret <https://github.com/TheLazyFerret>
+//! Copyright (c) 2025 TheLazyFerret
+//!   Licensed under the MIT license.
+//!   See LICENSE file in the project root for full license information.
+//!
+//! Import of Turing machines in the YAML format of turingmachine.io.
+//!
+//! The file has the `blank` symbol, the `start state`, and the `table` with the actions of each
+//! state. Each action is keyed by a symbol or a list of symbols, and it is either a direction, `L`
+//! or `R`, or a mapping with the symbol to `write`, if any, and the direction as the key of the
+//! next state, if it changes. The states without any action are the final ones, so halting there
+//! accepts the string, and halting anywhere else is halting without a defined transition.
+//!
+//! The YAML is loaded keeping where each node starts, so the configuration points to the right
+//! place of the file, as the ones read from toml.
+
+use crate::error::{Error, line_col};
+use crate::parser::{Import, RawState, RawSymbols, RawTransition, RawTuringMachine};
+use crate::pattern;
+use std::ops::Range;
+use toml::Spanned;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+/// Value of a node of a YAML document.
+enum Value {
+  Null,
+  Scalar(String),
+  Sequence(Vec<Node>),
+  /// The keys and the values, one after another.
+  Mapping(Vec<Node>),
+}
+
+/// Node of a YAML document, with the byte offset where it starts.
+struct Node {
+  value: Value,
+  start: usize,
+}
+
+impl Node {
+  /// Returns the text of a scalar node.
+  fn text(&self) -> Option<&str> {
+    match &self.value {
+      | Value::Scalar(x) => Some(x),
+      | _ => None,
+    }
+  }
+
+  /// Returns the keys and values of a mapping node.
+  fn entries(&self) -> Vec<(&Node, &Node)> {
+    match &self.value {
+      | Value::Mapping(x) => x.chunks(2).map(|x| (&x[0], &x[1])).collect(),
+      | _ => Vec::new(),
+    }
+  }
+
+  /// Returns the value of the key, if it is a mapping node with it.
+  fn get(&self, key: &str) -> Option<&Node> {
+    self.entries().into_iter().find(|x| x.0.text() == Some(key)).map(|x| x.1)
+  }
+}
+
+/// Builds the nodes of the first document from the events of the YAML parser.
+struct Loader<'a> {
+  source: &'a str,
+  /// Sequences and mappings not yet closed.
+  stack: Vec<Node>,
+  root: Option<Node>,
+  /// Offset of the first alias, as they aren't supported.
+  alias: Option<usize>,
+}
+
+impl MarkedEventReceiver for Loader<'_> {
+  fn on_event(&mut self, event: Event, mark: Marker) {
+    // The markers count characters, not bytes.
+    let start = self.source.char_indices().nth(mark.index()).map_or(self.source.len(), |x| x.0);
+    let node = match event {
+      | Event::Scalar(x, style, ..) => {
+        let null = style == TScalarStyle::Plain && matches!(x.as_str(), "" | "~" | "null");
+        Node { value: if null { Value::Null } else { Value::Scalar(x) }, start }
+      },
+      | Event::SequenceStart(..) => {
+        return self.stack.push(Node { value: Value::Sequence(Vec::new()), start });
+      },
+      | Event::MappingStart(..) => {
+        return self.stack.push(Node { value: Value::Mapping(Vec::new()), start });
+      },
+      | Event::SequenceEnd | Event::MappingEnd => self.stack.pop().unwrap(),
+      | Event::Alias(_) => {
+        self.alias.get_or_insert(start);
+        Node { value: Value::Null, start }
+      },
+      | _ => return,
+    };
+    match self.stack.last_mut() {
+      | Some(Node { value: Value::Sequence(x) | Value::Mapping(x), .. }) => x.push(node),
+      | _ => {
+        self.root.get_or_insert(node);
+      },
+    }
+  }
+}
+
+/// Returns an import error, with the location in the source where it was found.
+fn error(source: &str, offset: usize, message: &str) -> Error {
+  let (line, column) = line_col(source, offset);
+  Error::Import(format!("{message} (line {line}, column {column})"))
+}
+
+/// Returns the range from the offset to the end of its line.
+fn span(source: &str, start: usize) -> Range<usize> {
+  start..source[start..].find('\n').map_or(source.len(), |x| start + x)
+}
+
+/// Returns the single character of a symbol.
+fn symbol(source: &str, node: &Node) -> Result<char, Error> {
+  let text = node.text().unwrap_or_default();
+  let mut chars = text.chars();
+  match (chars.next(), chars.next()) {
+    | (Some(x), None) => Ok(x),
+    | _ => Err(error(source, node.start, "the symbol must be a single character")),
+  }
+}
+
+/// Returns the pattern read by the key of an action: a symbol, or a class for a list of them.
+fn read(source: &str, node: &Node) -> Result<String, Error> {
+  match &node.value {
+    | Value::Sequence(x) => {
+      let class: Result<String, Error> =
+        x.iter().map(|x| Ok(pattern::escape(symbol(source, x)?))).collect();
+      Ok(format!("[{}]", class?))
+    },
+    | _ => Ok(pattern::escape(symbol(source, node)?)),
+  }
+}
+
+/// Returns the transition of an action of the state.
+fn transition(
+  source: &str, state: &str, key: &Node, action: &Node,
+) -> Result<Spanned<RawTransition>, Error> {
+  let mut write = None;
+  let mut moves = None;
+  match &action.value {
+    | Value::Scalar(x) => moves = Some((x.as_str(), state)),
+    | Value::Mapping(_) => {
+      for (name, value) in action.entries() {
+        match (name.text(), value.text()) {
+          | (Some("write"), _) => write = Some(value),
+          | (Some(x), Some(next)) => moves = Some((x, next)),
+          | (Some(x), None) if matches!(value.value, Value::Null) => moves = Some((x, state)),
+          | _ => return Err(error(source, name.start, "the action has an unknown field")),
+        }
+      }
+    },
+    | _ => return Err(error(source, action.start, "the action must be a direction or a mapping")),
+  }
+  let (direction, next) = match moves {
+    | Some((x @ ("L" | "R"), next)) => (x, next),
+    | _ => return Err(error(source, action.start, "the action has no direction L or R")),
+  };
+  let write = match write {
+    | Some(x) => Spanned::new(span(source, x.start), pattern::escape(symbol(source, x)?)),
+    | None => Spanned::new(span(source, action.start), "=".to_string()),
+  };
+  let transition = RawTransition {
+    from: RawState::Name(state.to_string()),
+    next: RawState::Name(next.to_string()),
+    read: Spanned::new(span(source, key.start), RawSymbols::Chars(read(source, key)?)),
+    write: Spanned::new(write.span(), RawSymbols::Chars(write.into_inner())),
+    direction: Spanned::new(span(source, action.start), direction.to_string()),
+  };
+  Ok(Spanned::new(span(source, key.start), transition))
+}
+
+/// Import the Turing machine of the content of a turingmachine.io file.
+pub fn from_str(source: &str) -> Result<Import, Error> {
+  let mut loader = Loader { source, stack: Vec::new(), root: None, alias: None };
+  let mut parser = Parser::new_from_str(source);
+  parser.load(&mut loader, false).map_err(|x| Error::Import(x.to_string()))?;
+  if let Some(x) = loader.alias {
+    return Err(error(source, x, "aliases aren't supported"));
+  }
+  let root = match loader.root {
+    | Some(x) if matches!(x.value, Value::Mapping(_)) => x,
+    | _ => return Err(Error::Import("the file isn't a mapping".to_string())),
+  };
+  let field = |name: &str| {
+    let message = format!("the file has no {name}");
+    root.get(name).ok_or_else(|| error(source, root.start, &message))
+  };
+  let (blank, initial, table) = (field("blank")?, field("start state")?, field("table")?);
+  let blank = Spanned::new(span(source, blank.start), symbol(source, blank)?.to_string());
+  let Some(initial) = initial.text() else {
+    return Err(error(source, initial.start, "the start state must be a name"));
+  };
+  let mut warnings = Vec::new();
+  if root.get("input").is_some() {
+    warnings.push("the input of the file is left out".to_string());
+  }
+  let mut accept = Vec::new();
+  let mut transition = Vec::new();
+  for (state, actions) in table.entries() {
+    let Some(name) = state.text() else {
+      return Err(error(source, state.start, "the state must be a name"));
+    };
+    match &actions.value {
+      | Value::Null => accept.push(RawState::Name(name.to_string())),
+      | Value::Mapping(x) if x.is_empty() => accept.push(RawState::Name(name.to_string())),
+      | Value::Mapping(_) => {
+        for (key, action) in actions.entries() {
+          transition.push(self::transition(source, name, key, action)?);
+        }
+      },
+      | _ => return Err(error(source, actions.start, "the actions must be a mapping")),
+    }
+  }
+  let machine = RawTuringMachine {
+    ntapes: Spanned::new(span(source, table.start), 1),
+    initial: RawState::Name(initial.to_string()),
+    accept,
+    reject: Vec::new(),
+    nondeterministic: false,
+    budget: None,
+    output: None,
+    blank: Some(blank),
+    blank_glyph: None,
+    input_alphabet: None,
+    tape_alphabet: None,
+    max_steps: None,
+    timeout: None,
+    max_cells: None,
+    detect_cycles: false,
+    patterns: true,
+    transition,
+  };
+  Ok(Import { machine, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::error::Error;
+  use crate::parser;
+  use crate::turing_machine::Outcome;
+  use crate::turingmachine_io::from_str;
+
+  /// Machine that adds 1 to a binary number.
+  const MACHINE: &str = "
+input: '1011'
+blank: ' '
+start state: right
+table:
+  # Scan to the rightmost digit.
+  right:
+    [1,0]: R
+    ' '  : {L: carry}
+  carry:
+    1      : {write: 0, L}
+    [0,' ']: {write: 1, L: done}
+  done:
+";
+
+  #[test]
+  fn test_import() {
+    let import = from_str(MACHINE).unwrap();
+    assert_eq!(import.warnings, vec!["the input of the file is left out".to_string()]);
+    let tm = parser::parse(&import.machine).unwrap();
+    assert_eq!(tm.state_name(tm.initial()), "right");
+    let (outcome, output) = tm.run_transducer("1011", None).unwrap();
+    assert_eq!(outcome, Outcome::Accepted);
+    assert_eq!(output.unwrap().render(tm.symbols()), "1100");
+    assert_eq!(tm.run_transducer("11", None).unwrap().1.unwrap().render(tm.symbols()), "100");
+    let invalid = MACHINE.replace("{L: carry}", "{U: carry}");
+    let Err(Error::Import(x)) = from_str(&invalid) else { panic!("expected an import error") };
+    assert_eq!(x, "the action has no direction L or R (line 9, column 12)");
+  }
+}